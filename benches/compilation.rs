@@ -0,0 +1,16 @@
+mod fixtures;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fixtures::{compile, load_fixture};
+
+fn compilation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compilation");
+    for schema_name in &["small", "large", "deep-ref", "recursive-ref"] {
+        let raw_schema = load_fixture(&format!("{}-schema.json", schema_name));
+        let _ = group.bench_with_input(BenchmarkId::from_parameter(schema_name), &raw_schema, |b, raw_schema| b.iter(|| compile(raw_schema)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compilation);
+criterion_main!(benches);
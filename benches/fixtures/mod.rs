@@ -0,0 +1,25 @@
+use json_trait_rs::RustType;
+use jsonschema_validator::{DraftVersion, Schema, ScopeBuilder};
+use loader_rs::{loaders::RustTypeLoader, LoaderTrait};
+use std::{path::Path, sync::Arc};
+use url::Url;
+
+pub(crate) fn fixture_url(fixture_name: &str) -> Url {
+    Url::from_file_path(Path::new(env!("CARGO_MANIFEST_DIR")).join("test-data").join("benches").join(fixture_name)).unwrap()
+}
+
+pub(crate) fn load_fixture(fixture_name: &str) -> Arc<RustType> {
+    RustTypeLoader::default()
+        .get_or_fetch_with_result(&fixture_url(fixture_name))
+        .unwrap_or_else(|error| panic!("Unable to load fixture {}: {:?}", fixture_name, error))
+}
+
+// Compilation happens entirely in memory: the raw schema is injected into the loader cache
+// so that local references are resolved without touching the filesystem
+pub(crate) fn compile(raw_schema: &Arc<RustType>) -> Arc<Schema> {
+    let mut scope_builder = ScopeBuilder::create(DraftVersion::Draft4, RustTypeLoader::default());
+    let generated_url = scope_builder.inject_schema(raw_schema);
+    let schema = scope_builder.schema(&generated_url, &**raw_schema).expect("Benchmark schemas are supposed to be valid");
+    let _ = scope_builder.build();
+    schema
+}
@@ -0,0 +1,26 @@
+mod fixtures;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fixtures::{compile, load_fixture};
+
+fn validation(c: &mut Criterion) {
+    // small and large exercise the keyword validators, deep-ref exercises $ref resolution across nested instances
+    // and recursive-ref exercises a self-referencing (`{"$ref": "#"}`) tree schema against a deep tree instance
+    for schema_name in &["small", "large", "deep-ref", "recursive-ref"] {
+        let schema = compile(&load_fixture(&format!("{}-schema.json", schema_name)));
+        let mut group = c.benchmark_group(format!("validation/{}", schema_name));
+        for instance_kind in &["valid", "invalid"] {
+            let instance = load_fixture(&format!("{}-{}-instance.json", schema_name, instance_kind));
+            let _ = group.bench_with_input(BenchmarkId::new("is_valid", instance_kind), &*instance, |b, instance| {
+                b.iter(|| schema.is_valid("#", black_box(instance)))
+            });
+            let _ = group.bench_with_input(BenchmarkId::new("validation_errors", instance_kind), &*instance, |b, instance| {
+                b.iter(|| schema.validation_errors("#", black_box(instance)).count())
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, validation);
+criterion_main!(benches);
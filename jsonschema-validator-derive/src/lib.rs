@@ -0,0 +1,186 @@
+// Enable very pendantic clippy linting
+#![deny(clippy::pedantic, clippy::nursery)]
+#![forbid(unsafe_code)]
+
+extern crate proc_macro;
+
+mod serde_attributes;
+
+use crate::serde_attributes::{ContainerAttributes, FieldAttributes, RenameRule};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Error, Fields, Result, Type};
+
+// Generate the `jsonschema_validator::JsonSchema` implementation describing the serde representation of the type,
+// recursive types are referenced via `$ref` (see `macro_support::named_schema`)
+#[proc_macro_derive(JsonSchema, attributes(serde))]
+pub fn derive_json_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(|error| error.to_compile_error()).into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let container_attributes = ContainerAttributes::parse(&input.attrs)?;
+    let schema = match &input.data {
+        Data::Struct(data) => fields_schema(&data.fields, container_attributes.rename_all, &container_attributes)?,
+        Data::Enum(data) => enum_schema(data, &container_attributes)?,
+        Data::Union(_) => return Err(Error::new_spanned(&input.ident, "JsonSchema cannot be derived for unions")),
+    };
+
+    let name = &input.ident;
+    let schema_name = name.to_string();
+    let mut generics = input.generics.clone();
+    for type_param in generics.type_params_mut() {
+        type_param.bounds.push(parse_quote!(::jsonschema_validator::JsonSchema));
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::jsonschema_validator::JsonSchema for #name #type_generics #where_clause {
+            fn json_schema() -> ::jsonschema_validator::macro_support::RustType {
+                ::jsonschema_validator::macro_support::named_schema(::std::any::type_name::<Self>(), #schema_name, || #schema)
+            }
+        }
+    })
+}
+
+fn is_option(field_type: &Type) -> bool {
+    if let Type::Path(type_path) = field_type {
+        type_path.path.segments.last().map_or(false, |segment| segment.ident == "Option")
+    } else {
+        false
+    }
+}
+
+fn unraw(name: &str) -> &str {
+    name.trim_start_matches("r#")
+}
+
+fn fields_schema(fields: &Fields, rename_all: Option<RenameRule>, container_attributes: &ContainerAttributes) -> Result<TokenStream2> {
+    match fields {
+        Fields::Named(named_fields) => {
+            let mut properties = Vec::new();
+            let mut flattened_types = Vec::new();
+            for field in &named_fields.named {
+                let field_attributes = FieldAttributes::parse(&field.attrs)?;
+                let field_type = &field.ty;
+                if field_attributes.skip {
+                    continue;
+                } else if field_attributes.flatten {
+                    flattened_types.push(field_type);
+                    continue;
+                }
+
+                let field_name = field.ident.as_ref().map(ToString::to_string).unwrap_or_default();
+                let property_name = field_attributes
+                    .rename
+                    .unwrap_or_else(|| rename_all.map_or_else(|| unraw(&field_name).to_string(), |rule| rule.apply_to_field(unraw(&field_name))));
+                let is_required = !(is_option(field_type) || field_attributes.is_optional || container_attributes.default);
+                properties.push(quote! {
+                    (#property_name, <#field_type as ::jsonschema_validator::JsonSchema>::json_schema(), #is_required)
+                });
+            }
+            let deny_unknown_fields = container_attributes.deny_unknown_fields;
+            Ok(quote! {{
+                #[allow(unused_mut)]
+                let mut schema = ::jsonschema_validator::macro_support::object_schema(vec![#(#properties),*], #deny_unknown_fields);
+                #(
+                    ::jsonschema_validator::macro_support::flatten_schema(&mut schema, <#flattened_types as ::jsonschema_validator::JsonSchema>::json_schema());
+                )*
+                schema
+            }})
+        }
+        Fields::Unnamed(unnamed_fields) if unnamed_fields.unnamed.len() == 1 => {
+            let field_type = &unnamed_fields.unnamed[0].ty;
+            Ok(quote! { <#field_type as ::jsonschema_validator::JsonSchema>::json_schema() })
+        }
+        Fields::Unnamed(unnamed_fields) => {
+            let field_types = unnamed_fields.unnamed.iter().map(|field| &field.ty);
+            Ok(quote! {
+                ::jsonschema_validator::macro_support::tuple_schema(vec![#(<#field_types as ::jsonschema_validator::JsonSchema>::json_schema()),*])
+            })
+        }
+        Fields::Unit => Ok(quote! { <() as ::jsonschema_validator::JsonSchema>::json_schema() }),
+    }
+}
+
+fn enum_schema(data: &DataEnum, container_attributes: &ContainerAttributes) -> Result<TokenStream2> {
+    let mut variant_names = Vec::new();
+    let mut variant_schemas = Vec::new();
+    for variant in &data.variants {
+        let variant_attributes = FieldAttributes::parse(&variant.attrs)?;
+        if variant_attributes.skip {
+            continue;
+        }
+        let variant_name = variant_attributes.rename.unwrap_or_else(|| {
+            container_attributes
+                .rename_all
+                .map_or_else(|| variant.ident.to_string(), |rule| rule.apply_to_variant(&variant.ident.to_string()))
+        });
+        let is_unit = matches!(variant.fields, Fields::Unit);
+        let schema = fields_schema(&variant.fields, variant_attributes.rename_all, container_attributes)?;
+
+        let variant_schema = match (&container_attributes.tag, &container_attributes.content) {
+            _ if container_attributes.untagged => schema,
+            (Some(tag), Some(content)) if is_unit => quote! {
+                ::jsonschema_validator::macro_support::adjacently_tagged_schema(#tag, #content, #variant_name, None)
+            },
+            (Some(tag), Some(content)) => quote! {
+                ::jsonschema_validator::macro_support::adjacently_tagged_schema(#tag, #content, #variant_name, Some(#schema))
+            },
+            (Some(_), None) if matches!(&variant.fields, Fields::Unnamed(unnamed_fields) if unnamed_fields.unnamed.len() > 1) => {
+                return Err(Error::new_spanned(variant, "Internally tagged enums cannot contain tuple variants"));
+            }
+            (Some(tag), None) if is_unit => quote! {
+                ::jsonschema_validator::macro_support::internally_tagged_schema(#tag, #variant_name, ::jsonschema_validator::macro_support::object_schema(vec![], false))
+            },
+            (Some(tag), None) => quote! {
+                ::jsonschema_validator::macro_support::internally_tagged_schema(#tag, #variant_name, #schema)
+            },
+            (None, _) if is_unit => quote! {
+                ::jsonschema_validator::macro_support::string_enum_schema(&[#variant_name])
+            },
+            (None, _) => quote! {
+                ::jsonschema_validator::macro_support::externally_tagged_schema(#variant_name, #schema)
+            },
+        };
+        variant_names.push(variant_name);
+        variant_schemas.push((is_unit, variant_schema));
+    }
+
+    let is_externally_tagged = !container_attributes.untagged && container_attributes.tag.is_none();
+    if is_externally_tagged && variant_schemas.iter().all(|(is_unit, _)| *is_unit) {
+        // Enums with unit variants only are represented as strings
+        Ok(quote! { ::jsonschema_validator::macro_support::string_enum_schema(&[#(#variant_names),*]) })
+    } else {
+        let variant_schemas = variant_schemas.into_iter().map(|(_, variant_schema)| variant_schema);
+        Ok(quote! { ::jsonschema_validator::macro_support::one_of_schema(vec![#(#variant_schemas),*]) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::serde_attributes::RenameRule;
+
+    #[test]
+    fn rename_rules() {
+        let rules = [
+            RenameRule::Lower,
+            RenameRule::Upper,
+            RenameRule::Pascal,
+            RenameRule::Camel,
+            RenameRule::Snake,
+            RenameRule::ScreamingSnake,
+            RenameRule::Kebab,
+            RenameRule::ScreamingKebab,
+        ];
+        assert_eq!(
+            rules.iter().map(|rule| rule.apply_to_variant("VeryTasty")).collect::<Vec<_>>(),
+            ["verytasty", "VERYTASTY", "VeryTasty", "veryTasty", "very_tasty", "VERY_TASTY", "very-tasty", "VERY-TASTY"]
+        );
+        assert_eq!(
+            rules.iter().map(|rule| rule.apply_to_field("very_tasty")).collect::<Vec<_>>(),
+            ["very_tasty", "VERY_TASTY", "VeryTasty", "veryTasty", "very_tasty", "VERY_TASTY", "very-tasty", "VERY-TASTY"]
+        );
+    }
+}
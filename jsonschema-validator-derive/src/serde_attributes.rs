@@ -0,0 +1,166 @@
+use syn::{Attribute, Error, Lit, Meta, NestedMeta, Result};
+
+// Only the serde attributes affecting the JSON representation are considered, the others are ignored
+fn serde_attributes(attributes: &[Attribute]) -> Result<Vec<(String, Option<String>)>> {
+    let mut result = Vec::new();
+    for attribute in attributes.iter().filter(|attribute| attribute.path.is_ident("serde")) {
+        let meta_list = if let Meta::List(meta_list) = attribute.parse_meta()? {
+            meta_list
+        } else {
+            continue;
+        };
+        for nested_meta in meta_list.nested {
+            match nested_meta {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    if let Some(ident) = path.get_ident() {
+                        result.push((ident.to_string(), None));
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    if let (Some(ident), Lit::Str(value)) = (name_value.path.get_ident(), &name_value.lit) {
+                        result.push((ident.to_string(), Some(value.value())));
+                    }
+                }
+                // ie. `rename(serialize = "...", deserialize = "...")`, input is validated so the deserialize name is used
+                NestedMeta::Meta(Meta::List(inner_meta_list)) => {
+                    if let Some(ident) = inner_meta_list.path.get_ident() {
+                        for inner_nested_meta in &inner_meta_list.nested {
+                            if let NestedMeta::Meta(Meta::NameValue(name_value)) = inner_nested_meta {
+                                if let (true, Lit::Str(value)) = (name_value.path.is_ident("deserialize"), &name_value.lit) {
+                                    result.push((ident.to_string(), Some(value.value())));
+                                }
+                            }
+                        }
+                    }
+                }
+                NestedMeta::Lit(_) => {}
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(in crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(attributes: &[Attribute], value: &str) -> Result<Self> {
+        match value {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(Error::new_spanned(&attributes[0], format!("Unknown rename_all rule `{}`", value))),
+        }
+    }
+
+    // Same conversions of serde, variants are expected to be PascalCase
+    pub(in crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Pascal => variant.to_string(),
+            Self::Camel => variant[..1].to_ascii_lowercase() + &variant[1..],
+            Self::Snake => {
+                let mut snake = String::new();
+                for (index, character) in variant.char_indices() {
+                    if index > 0 && character.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(character.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    // Same conversions of serde, fields are expected to be snake_case
+    pub(in crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field
+                .split('_')
+                .map(|word| word.chars().take(1).flat_map(char::to_uppercase).chain(word.chars().skip(1)).collect::<String>())
+                .collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(in crate) struct ContainerAttributes {
+    pub(in crate) rename_all: Option<RenameRule>,
+    pub(in crate) tag: Option<String>,
+    pub(in crate) content: Option<String>,
+    pub(in crate) untagged: bool,
+    pub(in crate) deny_unknown_fields: bool,
+    pub(in crate) default: bool,
+}
+
+impl ContainerAttributes {
+    pub(in crate) fn parse(attributes: &[Attribute]) -> Result<Self> {
+        let mut result = Self::default();
+        for (name, value) in serde_attributes(attributes)? {
+            match (name.as_str(), value) {
+                ("rename_all", Some(value)) => result.rename_all = Some(RenameRule::parse(attributes, &value)?),
+                ("tag", Some(value)) => result.tag = Some(value),
+                ("content", Some(value)) => result.content = Some(value),
+                ("untagged", None) => result.untagged = true,
+                ("deny_unknown_fields", None) => result.deny_unknown_fields = true,
+                ("default", _) => result.default = true,
+                _ => {}
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(in crate) struct FieldAttributes {
+    pub(in crate) rename: Option<String>,
+    pub(in crate) rename_all: Option<RenameRule>,
+    pub(in crate) skip: bool,
+    pub(in crate) flatten: bool,
+    // The value could be missing from the input (`default` or `skip_serializing_if`)
+    pub(in crate) is_optional: bool,
+}
+
+impl FieldAttributes {
+    // Used for enum variants as well
+    pub(in crate) fn parse(attributes: &[Attribute]) -> Result<Self> {
+        let mut result = Self::default();
+        for (name, value) in serde_attributes(attributes)? {
+            match (name.as_str(), value) {
+                ("rename", Some(value)) => result.rename = Some(value),
+                ("rename_all", Some(value)) => result.rename_all = Some(RenameRule::parse(attributes, &value)?),
+                ("skip", None) | ("skip_deserializing", None) => result.skip = true,
+                ("flatten", None) => result.flatten = true,
+                ("default", _) | ("skip_serializing_if", Some(_)) => result.is_optional = true,
+                _ => {}
+            }
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,152 @@
+// JSON values written as macro input (ie. `schema!({"type": "object"})`) are parsed from the tokens, instead of their string
+// representation, so the span of every member is known and errors could point to the offending keyword.
+use json_trait_rs::RustType;
+use jsonschema_validator::macro_support::child_pointer;
+use proc_macro2::{Delimiter, Literal, Span, TokenStream, TokenTree};
+use std::collections::HashMap;
+use syn::{Error, Lit, Result};
+
+// Spans by JSON pointer, escaped as the fragments of the schema paths (so the paths reported by the compiler locate them)
+pub(in crate) type JsonSpans = HashMap<String, Span>;
+
+pub(in crate) fn parse_json(tokens: TokenStream) -> Result<(RustType, JsonSpans)> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut spans = HashMap::new();
+    let _ = spans.insert(String::new(), tokens.first().map_or_else(Span::call_site, TokenTree::span));
+    let value = parse_value(&tokens, Span::call_site(), "", &mut spans)?;
+    Ok((value, spans))
+}
+
+fn expected_value(span: Span) -> Error {
+    Error::new(span, "Expected a JSON value")
+}
+
+// tokens are all the tokens of a single value, span is used to report missing values
+fn parse_value(tokens: &[TokenTree], span: Span, pointer: &str, spans: &mut JsonSpans) -> Result<RustType> {
+    match tokens {
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace => parse_object(&group.stream().into_iter().collect::<Vec<_>>(), group.span(), pointer, spans),
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Bracket => parse_array(&group.stream().into_iter().collect::<Vec<_>>(), group.span(), pointer, spans),
+        // Values forwarded by macro_rules! could be wrapped in invisible groups
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => parse_value(&group.stream().into_iter().collect::<Vec<_>>(), group.span(), pointer, spans),
+        [TokenTree::Ident(ident)] if ident == "null" => Ok(RustType::Null),
+        [TokenTree::Ident(ident)] if ident == "true" => Ok(RustType::Boolean(true)),
+        [TokenTree::Ident(ident)] if ident == "false" => Ok(RustType::Boolean(false)),
+        [TokenTree::Literal(literal)] => parse_literal(literal, false),
+        [TokenTree::Punct(punct), TokenTree::Literal(literal)] if punct.as_char() == '-' => parse_literal(literal, true),
+        [] => Err(expected_value(span)),
+        [token, ..] => Err(expected_value(token.span())),
+    }
+}
+
+fn parse_literal(literal: &Literal, is_negative: bool) -> Result<RustType> {
+    match Lit::new(literal.clone()) {
+        Lit::Str(string) if !is_negative => Ok(RustType::String(string.value())),
+        Lit::Int(integer) => {
+            let value = integer.base10_parse::<i128>()?;
+            Ok(RustType::Integer(if is_negative { -value } else { value }))
+        }
+        Lit::Float(float) => {
+            let value = float.base10_parse::<f64>()?;
+            if value.is_finite() {
+                Ok(RustType::Number(if is_negative { -value } else { value }))
+            } else {
+                // ie. `1e999`, JSON numbers are finite
+                Err(Error::new(literal.span(), "Number is out of the range of f64"))
+            }
+        }
+        _ => Err(expected_value(literal.span())),
+    }
+}
+
+// Comma separated items, a trailing comma is allowed (as in `serde_json::json!`)
+fn split_items(tokens: &[TokenTree], span: Span) -> Result<Vec<&[TokenTree]>> {
+    let mut items = tokens.split(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')).collect::<Vec<_>>();
+    if items.last().map_or(false, |item| item.is_empty()) {
+        let _ = items.pop();
+    }
+    if items.iter().any(|item| item.is_empty()) {
+        Err(expected_value(span))
+    } else {
+        Ok(items)
+    }
+}
+
+fn parse_object(tokens: &[TokenTree], span: Span, pointer: &str, spans: &mut JsonSpans) -> Result<RustType> {
+    let mut object = HashMap::new();
+    for member in split_items(tokens, span)? {
+        match member {
+            [TokenTree::Literal(key_literal), TokenTree::Punct(colon), value @ ..] if colon.as_char() == ':' => {
+                let key = if let Lit::Str(key) = Lit::new(key_literal.clone()) {
+                    key.value()
+                } else {
+                    return Err(Error::new(key_literal.span(), "Expected a string key"));
+                };
+                let member_pointer = child_pointer(pointer, &key);
+                let _ = spans.insert(member_pointer.clone(), key_literal.span());
+                let value = parse_value(value, colon.span(), &member_pointer, spans)?;
+                if object.insert(key, value).is_some() {
+                    return Err(Error::new(key_literal.span(), "Duplicated key"));
+                }
+            }
+            _ => return Err(Error::new(member[0].span(), "Expected a `\"key\": value` member")),
+        }
+    }
+    Ok(RustType::Object(object))
+}
+
+fn parse_array(tokens: &[TokenTree], span: Span, pointer: &str, spans: &mut JsonSpans) -> Result<RustType> {
+    let mut array = Vec::new();
+    for (index, item) in split_items(tokens, span)?.into_iter().enumerate() {
+        let item_pointer = child_pointer(pointer, &index.to_string());
+        let _ = spans.insert(item_pointer.clone(), item[0].span());
+        array.push(parse_value(item, span, &item_pointer, spans)?);
+    }
+    Ok(RustType::List(array))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_json;
+    use json_trait_rs::rust_type;
+    use quote::quote;
+
+    #[test]
+    fn parse_json_tokens() {
+        let (value, spans) = parse_json(quote!({"type": "object", "properties": {"a": {"minimum": -1, "maximum": 1.5, "enum": [true, null,]}},})).unwrap();
+        assert_eq!(
+            value,
+            rust_type!({"type": "object", "properties": {"a": {"minimum": -1, "maximum": 1.5, "enum": [true, null]}}})
+        );
+        let mut pointers = spans.keys().map(String::as_str).collect::<Vec<_>>();
+        pointers.sort_unstable();
+        assert_eq!(
+            pointers,
+            vec![
+                "",
+                "/properties",
+                "/properties/a",
+                "/properties/a/enum",
+                "/properties/a/enum/0",
+                "/properties/a/enum/1",
+                "/properties/a/maximum",
+                "/properties/a/minimum",
+                "/type"
+            ]
+        );
+    }
+
+    #[test]
+    fn escape_pointers_as_url_fragments() {
+        let (_, spans) = parse_json(quote!({"properties": {"a b/c~é": {}}})).unwrap();
+        assert!(spans.contains_key("/properties/a%20b~1c~0%C3%A9"));
+    }
+
+    #[test]
+    fn parse_invalid_json_tokens() {
+        assert_eq!(parse_json(quote!({"type": object})).unwrap_err().to_string(), "Expected a JSON value");
+        assert_eq!(parse_json(quote!({"type": "a", "type": "b"})).unwrap_err().to_string(), "Duplicated key");
+        assert_eq!(parse_json(quote!({type: "object"})).unwrap_err().to_string(), "Expected a `\"key\": value` member");
+        assert_eq!(parse_json(quote!([1,, 2])).unwrap_err().to_string(), "Expected a JSON value");
+        assert_eq!(parse_json(quote!({"maximum": -1e999})).unwrap_err().to_string(), "Number is out of the range of f64");
+    }
+}
@@ -0,0 +1,183 @@
+// Enable very pendantic clippy linting
+#![deny(clippy::pedantic, clippy::nursery)]
+#![forbid(unsafe_code)]
+
+extern crate proc_macro;
+
+mod json_tokens;
+
+use crate::json_tokens::{parse_json, JsonSpans};
+use json_trait_rs::{JsonType, RustType};
+use jsonschema_validator::{
+    macro_support::{child_pointer, compile_embedded_schema},
+    DraftVersion, KeywordType, SchemaError,
+};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Literal, Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use std::{env, fs, path::Path};
+use syn::{Error, LitStr, Result};
+
+// Compiled schema of the JSON literal, ie. `schema!({"type": "object"})` or `schema!(OpenApi30, {"type": "object"})` (`Draft4` is the default).
+// The schema is compiled at build time, so malformed schemas are compiler errors, and it evaluates to a `&'static Arc<Schema>` compiled on first use.
+#[proc_macro]
+pub fn schema(input: TokenStream) -> TokenStream {
+    expand_schema(input.into()).unwrap_or_else(|error| error.to_compile_error()).into()
+}
+
+// Same as `schema!` for a JSON file, the path is relative to the directory containing the manifest of the crate (`CARGO_MANIFEST_DIR`)
+#[proc_macro]
+pub fn include_schema(input: TokenStream) -> TokenStream {
+    expand_include_schema(input.into()).unwrap_or_else(|error| error.to_compile_error()).into()
+}
+
+// Split the optional draft version (`Draft4, ...`) from the remaining input
+fn draft_version(input: TokenStream2) -> Result<(Ident, TokenStream2)> {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    match tokens.as_slice() {
+        [TokenTree::Ident(ident), TokenTree::Punct(punct), rest @ ..] if punct.as_char() == ',' && !["true", "false", "null"].iter().any(|value| ident == value) => {
+            if ["Draft4", "OpenApi30"].iter().any(|draft_version| ident == draft_version) {
+                Ok((ident.clone(), rest.iter().cloned().collect()))
+            } else {
+                Err(Error::new(ident.span(), "Unknown draft version, expected `Draft4` or `OpenApi30`"))
+            }
+        }
+        _ => Ok((Ident::new("Draft4", Span::call_site()), tokens.into_iter().collect())),
+    }
+}
+
+fn keyword_name(keyword: KeywordType) -> Option<&'static str> {
+    match keyword {
+        KeywordType::Unknown => None,
+        KeywordType::Type => Some("type"),
+        KeywordType::Properties => Some("properties"),
+        KeywordType::PatternProperties => Some("patternProperties"),
+        KeywordType::AdditionalProperties => Some("additionalProperties"),
+        KeywordType::Ref => Some("$ref"),
+        KeywordType::AllOf => Some("allOf"),
+        KeywordType::Discriminator => Some("discriminator"),
+        KeywordType::ReadOnly => Some("readOnly"),
+        KeywordType::WriteOnly => Some("writeOnly"),
+        KeywordType::Vocabulary => Some("$vocabulary"),
+        KeywordType::Custom(keyword) => Some(keyword),
+    }
+}
+
+// Compile the schema as it will be compiled at runtime, errors are located at the offending keyword (if spans are known)
+fn check_schema(draft_version: &Ident, raw_schema: &RustType, spans: &JsonSpans, default_span: Span) -> Result<()> {
+    let draft_version = if draft_version == "OpenApi30" { DraftVersion::OpenApi30 } else { DraftVersion::Draft4 };
+    match compile_embedded_schema(draft_version, raw_schema.clone()) {
+        Ok(_) => Ok(()),
+        Err(SchemaError::Malformed { path, keyword, detail }) => {
+            let pointer = path.fragment().unwrap_or("").trim_end_matches('/');
+            let keyword_pointer = keyword_name(keyword).map(|keyword| child_pointer(pointer, keyword));
+            let span = keyword_pointer
+                .as_ref()
+                .and_then(|keyword_pointer| spans.get(keyword_pointer))
+                .or_else(|| spans.get(pointer))
+                .copied()
+                .unwrap_or(default_span);
+            Err(Error::new(
+                span,
+                format!("Malformed schema: {} (at #{})", detail, keyword_pointer.as_deref().unwrap_or(pointer)),
+            ))
+        }
+        Err(SchemaError::LoaderError(error)) => Err(Error::new(
+            default_span,
+            format!("Loader Error: {} (references to other documents are not supported by embedded schemas)", error),
+        )),
+        Err(error) => Err(Error::new(default_span, error.to_string())),
+    }
+}
+
+fn rust_type_tokens(value: &RustType) -> TokenStream2 {
+    match value {
+        RustType::Null => quote! { ::jsonschema_validator::macro_support::RustType::Null },
+        RustType::Boolean(value) => quote! { ::jsonschema_validator::macro_support::RustType::Boolean(#value) },
+        RustType::String(value) => quote! { ::jsonschema_validator::macro_support::RustType::String(#value.to_string()) },
+        RustType::Integer(value) => {
+            let value = Literal::i128_suffixed(*value);
+            quote! { ::jsonschema_validator::macro_support::RustType::Integer(#value) }
+        }
+        RustType::Number(value) => {
+            let value = Literal::f64_suffixed(*value);
+            quote! { ::jsonschema_validator::macro_support::RustType::Number(#value) }
+        }
+        RustType::List(items) => {
+            let items = items.iter().map(rust_type_tokens);
+            quote! { ::jsonschema_validator::macro_support::RustType::List(vec![#(#items),*]) }
+        }
+        RustType::Object(object) => {
+            let keys = object.keys();
+            let values = object.values().map(rust_type_tokens);
+            quote! {
+                ::jsonschema_validator::macro_support::RustType::Object(vec![#((#keys.to_string(), #values)),*].into_iter().collect())
+            }
+        }
+    }
+}
+
+// The schema is compiled once, on first use, into a static local to the macro invocation
+fn lazy_schema(draft_version: &Ident, raw_schema: &RustType, extra_items: &TokenStream2) -> TokenStream2 {
+    let raw_schema = rust_type_tokens(raw_schema);
+    quote! {{
+        #extra_items
+        static SCHEMA: ::jsonschema_validator::macro_support::OnceCell<::std::sync::Arc<::jsonschema_validator::Schema>> =
+            ::jsonschema_validator::macro_support::OnceCell::new();
+        SCHEMA.get_or_init(|| {
+            ::jsonschema_validator::macro_support::compile_embedded_schema(::jsonschema_validator::DraftVersion::#draft_version, #raw_schema)
+                .expect("The schema is supposed to be valid as it was compiled at build time")
+        })
+    }}
+}
+
+fn expand_schema(input: TokenStream2) -> Result<TokenStream2> {
+    let (draft_version, json_tokens) = draft_version(input)?;
+    let (raw_schema, spans) = parse_json(json_tokens)?;
+    check_schema(&draft_version, &raw_schema, &spans, spans.get("").copied().unwrap_or_else(Span::call_site))?;
+    Ok(lazy_schema(&draft_version, &raw_schema, &TokenStream2::new()))
+}
+
+fn expand_include_schema(input: TokenStream2) -> Result<TokenStream2> {
+    let (draft_version, path_tokens) = draft_version(input)?;
+    let path_literal = syn::parse2::<LitStr>(path_tokens)?;
+    let manifest_directory = env::var("CARGO_MANIFEST_DIR").map_err(|error| Error::new(path_literal.span(), error))?;
+    let path = Path::new(&manifest_directory).join(path_literal.value());
+    let content = fs::read_to_string(&path).map_err(|error| Error::new(path_literal.span(), format!("{}: {}", path.display(), error)))?;
+    let raw_schema = serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|error| Error::new(path_literal.span(), format!("Invalid JSON: {}", error)))?
+        .to_rust_type();
+    // Spans of the file content are not known, errors are located at the path and report the JSON pointer of the keyword
+    check_schema(&draft_version, &raw_schema, &JsonSpans::new(), path_literal.span())?;
+    // The file is included as well, so the crate is rebuilt if it changes
+    let path = path.to_string_lossy();
+    Ok(lazy_schema(&draft_version, &raw_schema, &quote! { const _: &str = include_str!(#path); }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_schema, json_tokens::parse_json};
+    use jsonschema_validator::{macro_support::compile_embedded_schema, DraftVersion, SchemaError};
+    use quote::quote;
+
+    #[test]
+    fn malformed_schemas_report_the_offending_keyword() {
+        let message = expand_schema(quote!({"properties": {"name": {"type": 1}}})).unwrap_err().to_string();
+        assert!(message.starts_with("Malformed schema: "));
+        assert!(message.ends_with("(at #/properties/name/type)"));
+        assert_eq!(
+            expand_schema(quote!(Draft5, {})).unwrap_err().to_string(),
+            "Unknown draft version, expected `Draft4` or `OpenApi30`"
+        );
+    }
+
+    #[test]
+    fn malformed_schemas_are_located_by_escaped_keys() {
+        let (raw_schema, spans) = parse_json(quote!({"properties": {"a b/c~é": {"type": 1}}})).unwrap();
+        let pointer = match compile_embedded_schema(DraftVersion::Draft4, raw_schema) {
+            Err(SchemaError::Malformed { path, .. }) => path.fragment().unwrap_or("").to_string(),
+            result => panic!("Unexpected compilation result: {:?}", result),
+        };
+        assert!(spans.contains_key(&format!("{}/type", pointer)), "{} is not located", pointer);
+    }
+}
@@ -0,0 +1,40 @@
+use json_trait_rs::rust_type;
+use jsonschema_validator::{KeywordType, Schema, ValidationError};
+use jsonschema_validator_macros::{include_schema, schema};
+use std::sync::Arc;
+
+fn person_schema() -> &'static Arc<Schema> {
+    schema!({
+        "definitions": {"name": {"type": "string"}},
+        "type": "object",
+        "properties": {"name": {"$ref": "#/definitions/name"}, "age": {"type": ["integer", "null"]}},
+        "additionalProperties": false,
+    })
+}
+
+#[test]
+fn schema_is_compiled_once() {
+    assert!(Arc::ptr_eq(person_schema(), person_schema()));
+}
+
+#[test]
+fn embedded_schema_validates_instances() {
+    assert!(person_schema().is_valid("#", &rust_type!({"name": "Jane", "age": null})));
+    assert_eq!(
+        person_schema().validation_errors("#", &rust_type!({"name": 1})).collect::<Vec<_>>(),
+        vec![ValidationError::new("#/name", KeywordType::Type, "Invalid Type")]
+    );
+}
+
+#[test]
+fn openapi_embedded_schema() {
+    let schema = schema!(OpenApi30, {"type": "string", "nullable": true});
+    assert!(schema.is_valid("#", &rust_type!(null)));
+}
+
+#[test]
+fn included_schema_validates_instances() {
+    let schema = include_schema!("../test-data/cli/check/valid/address.json");
+    assert!(schema.is_valid("#", &rust_type!({"street": "Main Street"})));
+    assert!(!schema.is_valid("#", &rust_type!({"street": 1})));
+}
@@ -0,0 +1,162 @@
+use crate::common::{compile_schema, draft_arg, draft_from_arg, draft_version, error_format_arg, file_url, load_document, ErrorFormat, Outcome};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use jsonschema_validator::{DraftVersion, Schema, ScopeBuilder};
+use serde_json::json;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// Extensions of the files considered as schemas while walking directories
+const SCHEMA_EXTENSIONS: &[&str] = &["json", "yaml", "yml"];
+
+pub(in crate) fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("check")
+        .about("Check that schemas compile and that all their `$ref`s are resolvable")
+        .arg(draft_arg())
+        .arg(error_format_arg())
+        .arg(
+            Arg::with_name("meta-schema")
+                .long("meta-schema")
+                .takes_value(true)
+                .help("Validate the schemas against the given meta-schema as well"),
+        )
+        .arg(
+            Arg::with_name("paths")
+                .multiple(true)
+                .required(true)
+                .help("Schema files or directories (recursively walked for .json, .yaml and .yml files)"),
+        )
+}
+
+fn has_schema_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| SCHEMA_EXTENSIONS.contains(&extension))
+}
+
+// Files are reported in a deterministic order, so summaries of different runs could be compared
+fn schema_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        for entry in entries {
+            if entry.is_dir() || has_schema_extension(&entry) {
+                schema_files(&entry, files)?;
+            }
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+fn check_schema(path: &Path, draft_override: Option<DraftVersion>, meta_schema: Option<&Schema>) -> Result<Vec<String>, String> {
+    let raw_schema = load_document(path)?;
+    let draft_version = draft_version(&raw_schema, draft_override)?;
+    let schema_url = file_url(path)?;
+    let mut problems = Vec::new();
+
+    if let Some(meta_schema) = meta_schema {
+        problems.extend(
+            meta_schema
+                .yaml_validation_errors("#", &raw_schema)
+                .map(|validation_error| format!("{}: {} (meta-schema {})", validation_error.path(), validation_error.message(), validation_error.keyword())),
+        );
+    }
+
+    let mut scope_builder = ScopeBuilder::create_for_yaml(draft_version);
+    let dangling_references = scope_builder.dangling_references(&schema_url, &raw_schema);
+    for (location, reference, error) in &dangling_references {
+        problems.push(format!("#{}: $ref {} cannot be resolved ({})", location.fragment().unwrap_or(""), reference, error));
+    }
+
+    // Compilation would fail on the first dangling reference, which is already reported
+    if dangling_references.is_empty() {
+        if let Err(error) = scope_builder.yaml_schema(&schema_url, &raw_schema) {
+            problems.push(error.to_string());
+        }
+    }
+    let _ = scope_builder.build();
+    Ok(problems)
+}
+
+struct SchemaReport {
+    schema: String,
+    // Err means that the schema could not be checked at all
+    problems: Result<Vec<String>, String>,
+}
+
+impl SchemaReport {
+    fn is_valid(&self) -> bool {
+        matches!(&self.problems, Ok(problems) if problems.is_empty())
+    }
+
+    fn print_human(&self) {
+        match &self.problems {
+            Ok(problems) if problems.is_empty() => println!("{}: ok", self.schema),
+            Ok(problems) => {
+                println!("{}: failed", self.schema);
+                problems.iter().for_each(|problem| println!("  {}", problem));
+            }
+            Err(error) => println!("{}: error: {}", self.schema, error),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match &self.problems {
+            Ok(problems) => json!({"schema": self.schema, "valid": problems.is_empty(), "errors": problems}),
+            Err(error) => json!({"schema": self.schema, "valid": false, "errors": [error]}),
+        }
+    }
+}
+
+pub(in crate) fn run(matches: &ArgMatches<'_>) -> Outcome {
+    let draft_override = draft_from_arg(matches.value_of("draft"));
+    let error_format = ErrorFormat::from_arg(matches.value_of("error-format"));
+
+    let meta_schema = match matches
+        .value_of("meta-schema")
+        .map(|meta_schema_path| (meta_schema_path, compile_schema(Path::new(meta_schema_path), draft_override)))
+    {
+        None => None,
+        Some((_, Ok(meta_schema))) => Some(meta_schema),
+        Some((meta_schema_path, Err(error))) => {
+            match error_format {
+                ErrorFormat::Human => eprintln!("{}: error: {}", meta_schema_path, error),
+                ErrorFormat::Json => println!("{}", json!({"schema": meta_schema_path, "error": error})),
+            }
+            return Outcome::Error;
+        }
+    };
+
+    let mut reports = Vec::new();
+    for path in matches.values_of("paths").into_iter().flatten() {
+        let mut files = Vec::new();
+        if let Err(error) = schema_files(Path::new(path), &mut files) {
+            reports.push(SchemaReport {
+                schema: path.to_string(),
+                problems: Err(format!("Unable to walk {}: {}", path, error)),
+            });
+        }
+        reports.extend(files.iter().map(|file| SchemaReport {
+            schema: file.display().to_string(),
+            problems: check_schema(file, draft_override, meta_schema.as_deref()),
+        }));
+    }
+
+    let failed = reports.iter().filter(|report| !report.is_valid()).count();
+    match error_format {
+        ErrorFormat::Human => {
+            reports.iter().for_each(SchemaReport::print_human);
+            println!("{} schemas checked, {} failed", reports.len(), failed);
+        }
+        ErrorFormat::Json => println!("{}", json!({"schemas": reports.iter().map(SchemaReport::to_json).collect::<Vec<_>>()})),
+    }
+
+    if failed == 0 && !reports.is_empty() {
+        Outcome::Valid
+    } else {
+        Outcome::Error
+    }
+}
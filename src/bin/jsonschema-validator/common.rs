@@ -0,0 +1,94 @@
+use clap::Arg;
+use json_trait_rs::JsonType;
+use jsonschema_validator::{DraftVersion, Schema, ScopeBuilder};
+use serde_yaml::Value;
+use std::{ffi::OsStr, fs, path::Path, sync::Arc};
+use url::Url;
+
+const DRAFT4_META_SCHEMA: &str = "http://json-schema.org/draft-04/schema";
+
+// Exit codes shared by all the subcommands, the highest outcome of the processed files is reported
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(in crate) enum Outcome {
+    Valid = 0,
+    Invalid = 1,
+    Error = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(in crate) enum ErrorFormat {
+    Human,
+    Json,
+}
+
+impl ErrorFormat {
+    pub(in crate) fn from_arg(value: Option<&str>) -> Self {
+        match value {
+            Some("json") => Self::Json,
+            _ => Self::Human,
+        }
+    }
+}
+
+pub(in crate) fn draft_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("draft")
+        .long("draft")
+        .takes_value(true)
+        .possible_values(&["draft4", "openapi30"])
+        .help("Draft used to compile the schemas, instead of the one declared by `$schema`")
+}
+
+pub(in crate) fn error_format_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("error-format")
+        .long("error-format")
+        .takes_value(true)
+        .possible_values(&["human", "json"])
+        .default_value("human")
+        .help("Format of the reported results")
+}
+
+pub(in crate) fn draft_from_arg(value: Option<&str>) -> Option<DraftVersion> {
+    match value {
+        Some("draft4") => Some(DraftVersion::Draft4),
+        Some("openapi30") => Some(DraftVersion::OpenApi30),
+        _ => None,
+    }
+}
+
+// `.json` files are parsed as JSON, so YAML only syntax is rejected. Every other file is parsed as YAML (ie. `.yaml` and `.yml`)
+pub(in crate) fn load_document(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path).map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+    let document = match path.extension().and_then(OsStr::to_str) {
+        Some("json") => serde_json::from_str(&content).map_err(|error| error.to_string()),
+        _ => serde_yaml::from_str(&content).map_err(|error| error.to_string()),
+    };
+    document.map_err(|error| format!("Unable to parse {}: {}", path.display(), error))
+}
+
+// Absolute url of path, local `$ref`s are resolved relatively to it
+pub(in crate) fn file_url(path: &Path) -> Result<Url, String> {
+    let absolute_path = path.canonicalize().map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+    Url::from_file_path(&absolute_path).map_err(|_| format!("Unable to represent {} as url", path.display()))
+}
+
+pub(in crate) fn draft_version(raw_schema: &Value, draft_override: Option<DraftVersion>) -> Result<DraftVersion, String> {
+    if let Some(draft_version) = draft_override {
+        return Ok(draft_version);
+    }
+
+    match raw_schema.get_attribute("$schema").and_then(JsonType::as_string) {
+        None => Ok(DraftVersion::default()),
+        Some(meta_schema) if meta_schema.trim_end_matches('#') == DRAFT4_META_SCHEMA => Ok(DraftVersion::Draft4),
+        Some(meta_schema) => Err(format!("Unsupported $schema {} (use --draft to override it)", meta_schema)),
+    }
+}
+
+pub(in crate) fn compile_schema(path: &Path, draft_override: Option<DraftVersion>) -> Result<Arc<Schema>, String> {
+    let raw_schema = load_document(path)?;
+    let draft_version = draft_version(&raw_schema, draft_override)?;
+    let schema_url = file_url(path)?;
+    let mut scope_builder = ScopeBuilder::create_for_yaml(draft_version);
+    let schema = scope_builder.yaml_schema(&schema_url, &raw_schema).map_err(|error| error.to_string())?;
+    let _ = scope_builder.build();
+    Ok(schema)
+}
@@ -0,0 +1,37 @@
+// Enable very pendantic clippy linting
+#![deny(clippy::pedantic, clippy::nursery)]
+#![forbid(unsafe_code)]
+
+mod check;
+mod common;
+mod validate;
+
+use crate::common::Outcome;
+use clap::{App, AppSettings};
+use std::process;
+
+fn main() {
+    let matches = match App::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(validate::subcommand())
+        .subcommand(check::subcommand())
+        .get_matches_safe()
+    {
+        Ok(matches) => matches,
+        // Usage errors are reported with the same exit code of schema errors, so they are not mistaken for invalid instances
+        Err(error) if error.use_stderr() => {
+            eprintln!("{}", error.message);
+            process::exit(Outcome::Error as i32);
+        }
+        Err(error) => error.exit(),
+    };
+
+    let outcome = match matches.subcommand() {
+        ("validate", Some(sub_matches)) => validate::run(sub_matches),
+        ("check", Some(sub_matches)) => check::run(sub_matches),
+        _ => unreachable!("clap guarantees that a known subcommand is provided"),
+    };
+    process::exit(outcome as i32);
+}
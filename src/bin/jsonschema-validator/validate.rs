@@ -0,0 +1,213 @@
+use crate::common::{compile_schema, draft_arg, draft_from_arg, error_format_arg, load_document, ErrorFormat, Outcome};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use jsonschema_validator::{NdjsonValidator, Schema, ValidationError};
+use serde_json::json;
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    sync::Arc,
+};
+
+pub(in crate) fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("validate")
+        .about("Validate JSON or YAML instances against a schema")
+        .arg(
+            Arg::with_name("schema")
+                .long("schema")
+                .short("s")
+                .takes_value(true)
+                .required(true)
+                .help("Schema file, local `$ref`s are resolved relatively to it"),
+        )
+        .arg(draft_arg())
+        .arg(error_format_arg())
+        .arg(
+            Arg::with_name("ndjson")
+                .long("ndjson")
+                .help("Instances are newline-delimited JSON files (`-` for stdin), each line is validated as a record"),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .long("jobs")
+                .short("j")
+                .takes_value(true)
+                .requires("ndjson")
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(0) => Err("at least one thread is required".to_string()),
+                    Ok(_) => Ok(()),
+                    Err(error) => Err(error.to_string()),
+                })
+                .help("Number of threads validating the newline-delimited JSON records"),
+        )
+        .arg(Arg::with_name("instances").multiple(true).required(true).help("Instance files to validate"))
+}
+
+fn validation_errors_json(validation_errors: &[ValidationError]) -> Vec<serde_json::Value> {
+    validation_errors
+        .iter()
+        .map(|validation_error| {
+            json!({
+                "path": validation_error.path(),
+                "keyword": validation_error.keyword().to_string(),
+                "message": validation_error.message(),
+            })
+        })
+        .collect()
+}
+
+fn print_human_validation_errors(validation_errors: &[ValidationError]) {
+    for validation_error in validation_errors {
+        println!("  {}: {} ({})", validation_error.path(), validation_error.message(), validation_error.keyword());
+    }
+}
+
+struct InstanceReport<'a> {
+    instance: &'a str,
+    // Err means that the instance could not be validated at all
+    validation_errors: Result<Vec<ValidationError>, String>,
+}
+
+impl InstanceReport<'_> {
+    fn outcome(&self) -> Outcome {
+        match &self.validation_errors {
+            Ok(validation_errors) if validation_errors.is_empty() => Outcome::Valid,
+            Ok(_) => Outcome::Invalid,
+            Err(_) => Outcome::Error,
+        }
+    }
+
+    fn print_human(&self) {
+        match &self.validation_errors {
+            Ok(validation_errors) if validation_errors.is_empty() => println!("{}: valid", self.instance),
+            Ok(validation_errors) => {
+                println!("{}: invalid", self.instance);
+                print_human_validation_errors(validation_errors);
+            }
+            Err(error) => println!("{}: error: {}", self.instance, error),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match &self.validation_errors {
+            Ok(validation_errors) => json!({
+                "instance": self.instance,
+                "valid": validation_errors.is_empty(),
+                "errors": validation_errors_json(validation_errors),
+            }),
+            Err(error) => json!({"instance": self.instance, "error": error}),
+        }
+    }
+}
+
+pub(in crate) fn run(matches: &ArgMatches<'_>) -> Outcome {
+    let schema_path = matches.value_of("schema").unwrap_or_default();
+    let error_format = ErrorFormat::from_arg(matches.value_of("error-format"));
+
+    let schema = match compile_schema(Path::new(schema_path), draft_from_arg(matches.value_of("draft"))) {
+        Ok(schema) => schema,
+        Err(error) => {
+            match error_format {
+                ErrorFormat::Human => eprintln!("{}: error: {}", schema_path, error),
+                ErrorFormat::Json => println!("{}", json!({"schema": schema_path, "error": error})),
+            }
+            return Outcome::Error;
+        }
+    };
+
+    if matches.is_present("ndjson") {
+        let workers = matches.value_of("jobs").map_or(1, |jobs| jobs.parse().expect("jobs is verified by its validator"));
+        return matches
+            .values_of("instances")
+            .into_iter()
+            .flatten()
+            .map(|instance| validate_ndjson(&schema, instance, workers, error_format))
+            .max()
+            .unwrap_or(Outcome::Valid);
+    }
+
+    let reports = matches
+        .values_of("instances")
+        .into_iter()
+        .flatten()
+        .map(|instance| InstanceReport {
+            instance,
+            validation_errors: load_document(Path::new(instance)).map(|value| schema.yaml_validation_errors("#", &value).collect()),
+        })
+        .collect::<Vec<_>>();
+
+    match error_format {
+        ErrorFormat::Human => reports.iter().for_each(InstanceReport::print_human),
+        ErrorFormat::Json => println!(
+            "{}",
+            json!({"schema": schema_path, "instances": reports.iter().map(InstanceReport::to_json).collect::<Vec<_>>()})
+        ),
+    }
+
+    reports.iter().map(InstanceReport::outcome).max().unwrap_or(Outcome::Valid)
+}
+
+// Records are reported while they are validated. Human format reports only the invalid records and a summary,
+// JSON format reports one JSON document per line, so the output is newline-delimited JSON as well.
+fn validate_ndjson(schema: &Arc<Schema>, instance: &str, workers: usize, error_format: ErrorFormat) -> Outcome {
+    let reader: Box<dyn Read> = if instance == "-" {
+        Box::new(io::stdin())
+    } else {
+        match File::open(instance) {
+            Ok(file) => Box::new(file),
+            Err(error) => {
+                let error = format!("Unable to read {}: {}", instance, error);
+                match error_format {
+                    ErrorFormat::Human => println!("{}: error: {}", instance, error),
+                    ErrorFormat::Json => println!("{}", json!({"instance": instance, "error": error})),
+                }
+                return Outcome::Error;
+            }
+        }
+    };
+
+    let (mut records, mut invalid_records) = (0, 0);
+    for record in NdjsonValidator::create(schema.clone(), reader).use_workers(workers) {
+        match record {
+            Ok(record) => {
+                records += 1;
+                if !record.is_valid() {
+                    invalid_records += 1;
+                }
+                match error_format {
+                    ErrorFormat::Human if record.is_valid() => {}
+                    ErrorFormat::Human => {
+                        println!("{}:{}: invalid", instance, record.line());
+                        print_human_validation_errors(record.validation_errors());
+                    }
+                    ErrorFormat::Json => println!(
+                        "{}",
+                        json!({
+                            "instance": instance,
+                            "line": record.line(),
+                            "valid": record.is_valid(),
+                            "errors": validation_errors_json(record.validation_errors()),
+                        })
+                    ),
+                }
+            }
+            Err(error) => {
+                let error = format!("Unable to read {}: {}", instance, error);
+                match error_format {
+                    ErrorFormat::Human => println!("{}: error: {}", instance, error),
+                    ErrorFormat::Json => println!("{}", json!({"instance": instance, "error": error})),
+                }
+                return Outcome::Error;
+            }
+        }
+    }
+
+    if error_format == ErrorFormat::Human {
+        println!("{}: {} records, {} invalid", instance, records, invalid_records);
+    }
+    if invalid_records == 0 {
+        Outcome::Valid
+    } else {
+        Outcome::Invalid
+    }
+}
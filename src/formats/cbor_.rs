@@ -0,0 +1,82 @@
+// JsonType cannot be implemented for serde_cbor::Value (JsonType::as_array and JsonType::as_object have to return
+// references to the same type), so CBOR values are converted into RustType before validation according to the following rules:
+//  * null, booleans, integers, floats, text strings and arrays map to their JSON counterpart
+//  * maps are objects, keys have to be text strings
+//  * tags are transparent, the tagged value is validated (ie. dates are validated as strings or numbers)
+//  * byte strings, non finite floats, undefined and simple values have no JSON equivalent and are reported as validation errors
+use crate::types::{keyword_type::KeywordType, schema::Schema, validation_error::ValidationError, validator_error_iterator::ValidationErrorIterator};
+use json_trait_rs::RustType;
+use serde_cbor::Value;
+use std::collections::HashMap;
+
+// Error is reported as (instance location, reason)
+fn cbor_to_rust_type(path: &str, value: &Value) -> Result<RustType, (String, &'static str)> {
+    match value {
+        Value::Null => Ok(RustType::Null),
+        Value::Bool(boolean) => Ok(RustType::Boolean(*boolean)),
+        Value::Integer(integer) => Ok(RustType::Integer(*integer)),
+        Value::Float(number) if number.is_finite() => Ok(RustType::Number(*number)),
+        Value::Float(_) => Err((path.to_string(), "Float is not finite")),
+        Value::Text(text) => Ok(RustType::String(text.clone())),
+        Value::Bytes(_) => Err((path.to_string(), "Byte string has no JSON equivalent")),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| cbor_to_rust_type(&format!("{}/{}", path, index), item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(RustType::List),
+        Value::Map(map) => map
+            .iter()
+            .map(|(key, item)| {
+                if let Value::Text(key) = key {
+                    Ok((key.clone(), cbor_to_rust_type(&format!("{}/{}", path, key), item)?))
+                } else {
+                    Err((path.to_string(), "Map key is not a text string"))
+                }
+            })
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map(RustType::Object),
+        Value::Tag(_, tagged_value) => cbor_to_rust_type(path, tagged_value),
+        _ => Err((path.to_string(), "Value has no JSON equivalent")),
+    }
+}
+
+impl Schema {
+    /// Parts of value without JSON equivalent are reported as validation errors, as they could not be validated.
+    pub fn cbor_validation_errors(&self, path: &str, value: &Value) -> ValidationErrorIterator {
+        match cbor_to_rust_type(path, value) {
+            Ok(rust_type_value) => self.validation_errors(path, &rust_type_value),
+            Err((location, reason)) => ValidationErrorIterator::from(ValidationError::new(&location, KeywordType::Unknown, reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{keyword_type::KeywordType, schema::initialised_draft4_schema, validation_error::ValidationError};
+    use json_trait_rs::rust_type;
+    use serde_cbor::Value;
+    use std::collections::BTreeMap;
+    use test_case::test_case;
+
+    fn object(items: Vec<(Value, Value)>) -> Value {
+        Value::Map(items.into_iter().collect::<BTreeMap<_, _>>())
+    }
+
+    fn text(value: &str) -> Value {
+        Value::Text(value.to_string())
+    }
+
+    #[test_case(object(vec![(text("id"), Value::Integer(1)), (text("name"), text("n"))]) => Vec::<ValidationError>::new())]
+    #[test_case(object(vec![(text("id"), Value::Tag(2, Box::new(Value::Integer(1))))]) => Vec::<ValidationError>::new())]
+    #[test_case(object(vec![(text("id"), text("1"))]) => vec![ValidationError::new("#/id", KeywordType::Type, "Invalid Type")])]
+    #[test_case(object(vec![(text("name"), Value::Bytes(vec![1]))]) => vec![ValidationError::new("#/name", KeywordType::Unknown, "Byte string has no JSON equivalent")])]
+    #[test_case(object(vec![(Value::Integer(1), text("one"))]) => vec![ValidationError::new("#", KeywordType::Unknown, "Map key is not a text string")])]
+    #[test_case(Value::Array(vec![Value::Float(f64::NAN)]) => vec![ValidationError::new("#/0", KeywordType::Unknown, "Float is not finite")])]
+    fn validate_cbor_value(value: Value) -> Vec<ValidationError> {
+        initialised_draft4_schema(rust_type!({"properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}))
+            .expect("Schema is supposed to be valid")
+            .cbor_validation_errors("#", &value)
+            .collect()
+    }
+}
@@ -0,0 +1,361 @@
+// JSON documents read from a reader are validated while they are parsed, without materialising them.
+// At every value the applied keywords are collected (`$ref` and `allOf` are expanded), then:
+//  * `type`, `readOnly` and `writeOnly` are evaluated as soon as the kind of the value is known
+//  * `properties`, `patternProperties` and `additionalProperties` select the keywords applied to each member
+//  * values without applied keywords (ie. array items, as `items` is not supported yet) are skipped
+//  * subtrees where `discriminator` or custom keywords are applied are buffered, as those keywords need the whole value
+//  * members occurring more than once in an object are reported (the tree keeps only the last occurrence), only the first occurrence is validated
+// The reported validation errors are the same of the tree based validation, but they follow the order of the document.
+use crate::{
+    keywords::DraftValidator,
+    types::{keyword_type::KeywordType, schema::Schema, validation_error::ValidationError, validator_error_iterator::ValidationErrorIterator},
+};
+use json_trait_rs::RustType;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Formatter},
+    io::{BufReader, Read},
+    marker::PhantomData,
+    slice::Iter,
+    sync::Arc,
+};
+
+// Schemas applying to a value. The schemas reached via `$ref` are retained here, as recursive references do not retain them,
+// so that their validators can be borrowed while the value is validated.
+struct AppliedSchemas<'s> {
+    schemas: Vec<&'s Schema>,
+    // Schemas reached via `$ref`, in the order they are visited by applied_validators
+    referenced_schemas: Vec<Option<Arc<Schema>>>,
+}
+
+impl<'s> AppliedSchemas<'s> {
+    fn new(schemas: Vec<&'s Schema>) -> Self {
+        let mut referenced_schemas = Vec::new();
+        schemas.iter().for_each(|schema| collect_referenced_schemas(schema, &mut referenced_schemas));
+        Self { schemas, referenced_schemas }
+    }
+
+    fn validators(&self) -> Vec<&DraftValidator> {
+        let mut referenced_schemas = self.referenced_schemas.iter();
+        let mut validators = Vec::new();
+        self.schemas.iter().for_each(|schema| applied_validators(schema, &mut referenced_schemas, &mut validators));
+        validators
+    }
+}
+
+fn collect_referenced_schemas(schema: &Schema, referenced_schemas: &mut Vec<Option<Arc<Schema>>>) {
+    for validator in &schema.validators {
+        match validator {
+            DraftValidator::Ref(ref_validator) => {
+                let referenced_schema = ref_validator.referenced_schema();
+                referenced_schemas.push(referenced_schema.clone());
+                if let Some(referenced_schema) = referenced_schema {
+                    collect_referenced_schemas(&referenced_schema, referenced_schemas);
+                }
+            }
+            DraftValidator::AllOf(all_of_validator) => all_of_validator.schemas.iter().for_each(|schema| collect_referenced_schemas(schema, referenced_schemas)),
+            _ => {}
+        }
+    }
+}
+
+fn applied_validators<'s>(schema: &'s Schema, referenced_schemas: &mut Iter<'s, Option<Arc<Schema>>>, validators: &mut Vec<&'s DraftValidator>) {
+    for validator in &schema.validators {
+        match validator {
+            DraftValidator::Ref(_) => {
+                if let Some(Some(referenced_schema)) = referenced_schemas.next() {
+                    applied_validators(referenced_schema, referenced_schemas, validators);
+                } else {
+                    // Unresolved references are reported by the reference itself
+                    validators.push(validator);
+                }
+            }
+            DraftValidator::AllOf(all_of_validator) => all_of_validator
+                .schemas
+                .iter()
+                .for_each(|schema| applied_validators(schema, referenced_schemas, validators)),
+            _ => validators.push(validator),
+        }
+    }
+}
+
+fn needs_whole_value(validator: &DraftValidator) -> bool {
+    matches!(validator, DraftValidator::Discriminator(_) | DraftValidator::Custom(_))
+}
+
+// Value of the document being validated, validation errors are accumulated in validation_errors
+struct ValueSeed<'s, 'e> {
+    path: String,
+    validators: Vec<&'s DraftValidator>,
+    validation_errors: &'e mut Vec<ValidationError>,
+}
+
+impl ValueSeed<'_, '_> {
+    // value is either the whole value or, for objects and arrays, an empty value of the same type
+    fn validate(&mut self, value: &RustType) {
+        for validator in &self.validators {
+            self.validation_errors.extend(validator.validation_errors(&self.path, value));
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(mut self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.validators.is_empty() {
+            let _ = IgnoredAny::deserialize(deserializer)?;
+            Ok(())
+        } else if self.validators.iter().any(|validator| needs_whole_value(validator)) {
+            let value = RustTypeSeed.deserialize(deserializer)?;
+            self.validate(&value);
+            Ok(())
+        } else {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for ValueSeed<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: Error>(mut self, value: bool) -> Result<Self::Value, E> {
+        self.validate(&RustType::Boolean(value));
+        Ok(())
+    }
+
+    fn visit_i64<E: Error>(mut self, value: i64) -> Result<Self::Value, E> {
+        self.validate(&RustType::Integer(i128::from(value)));
+        Ok(())
+    }
+
+    fn visit_u64<E: Error>(mut self, value: u64) -> Result<Self::Value, E> {
+        self.validate(&RustType::Integer(i128::from(value)));
+        Ok(())
+    }
+
+    fn visit_f64<E: Error>(mut self, value: f64) -> Result<Self::Value, E> {
+        self.validate(&RustType::Number(value));
+        Ok(())
+    }
+
+    fn visit_str<E: Error>(mut self, value: &str) -> Result<Self::Value, E> {
+        self.validate(&RustType::String(value.to_string()));
+        Ok(())
+    }
+
+    fn visit_unit<E: Error>(mut self) -> Result<Self::Value, E> {
+        self.validate(&RustType::Null);
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+        self.validate(&RustType::List(Vec::new()));
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        self.validate(&RustType::Object(HashMap::new()));
+        let mut property_names = HashSet::new();
+        while let Some(property_name) = map.next_key::<String>()? {
+            let property_path = format!("{}/{}", self.path, property_name);
+            if !property_names.insert(property_name.clone()) {
+                self.validation_errors.push(ValidationError::new(&property_path, KeywordType::Unknown, "Duplicated member"));
+                let _ = map.next_value::<IgnoredAny>()?;
+                continue;
+            }
+            let mut property_schemas: Vec<&Schema> = Vec::new();
+            for validator in &self.validators {
+                match validator {
+                    DraftValidator::Properties(properties_validator) => {
+                        if let Some(schema) = properties_validator.properties.get(&property_name) {
+                            property_schemas.push(schema);
+                        }
+                    }
+                    DraftValidator::PatternProperties(pattern_properties_validator) => {
+                        for (_, schema) in pattern_properties_validator.patterns.iter().filter(|(regex, _)| regex.is_match(&property_name)) {
+                            property_schemas.push(schema);
+                        }
+                    }
+                    DraftValidator::AdditionalProperties(additional_properties_validator) if additional_properties_validator.is_additional(&property_name) => {
+                        if let Some(schema) = &additional_properties_validator.schema {
+                            property_schemas.push(schema);
+                        } else {
+                            self.validation_errors.push(ValidationError::new(
+                                &property_path,
+                                KeywordType::AdditionalProperties,
+                                "Additional property is not allowed",
+                            ));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let property_schemas = AppliedSchemas::new(property_schemas);
+            map.next_value_seed(ValueSeed {
+                path: property_path,
+                validators: property_schemas.validators(),
+                validation_errors: &mut *self.validation_errors,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+// Buffered value, used when the whole value is needed for validation
+struct RustTypeSeed;
+
+impl<'de> DeserializeSeed<'de> for RustTypeSeed {
+    type Value = RustType;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RustTypeSeed {
+    type Value = RustType;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(RustType::Boolean(value))
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(RustType::Integer(i128::from(value)))
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(RustType::Integer(i128::from(value)))
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(RustType::Number(value))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(RustType::String(value.to_string()))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(RustType::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(Self)? {
+            items.push(item);
+        }
+        Ok(RustType::List(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = HashMap::new();
+        while let Some((property_name, property_value)) = map.next_entry_seed(PhantomData::<String>, Self)? {
+            let _ = object.insert(property_name, property_value);
+        }
+        Ok(RustType::Object(object))
+    }
+}
+
+impl Schema {
+    /// Validate the JSON document read from reader while it is parsed, documents that are not valid JSON are reported as validation errors.
+    pub fn json_reader_validation_errors<R: Read>(&self, path: &str, reader: R) -> ValidationErrorIterator {
+        let applied_schemas = AppliedSchemas::new(vec![self]);
+        let mut validation_errors = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        let result = ValueSeed {
+            path: path.to_string(),
+            validators: applied_schemas.validators(),
+            validation_errors: &mut validation_errors,
+        }
+        .deserialize(&mut deserializer)
+        .and_then(|()| deserializer.end());
+        if let Err(error) = result {
+            validation_errors.push(ValidationError::new(path, KeywordType::Unknown, &format!("Invalid JSON: {}", error)));
+        }
+        ValidationErrorIterator::new(validation_errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, validation_error::ValidationError};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use test_case::test_case;
+
+    fn person_schema() -> Arc<Schema> {
+        Schema::from_serde_json(
+            DraftVersion::Draft4,
+            &json!({
+                "definitions": {"name": {"type": "string"}, "port": {"type": "integer"}},
+                "type": "object",
+                "properties": {
+                    "name": {"$ref": "#/definitions/name"},
+                    "address": {
+                        "allOf": [{"properties": {"street": {"type": "string"}}}, {"properties": {"number": {"type": "integer"}}}],
+                        "additionalProperties": false,
+                    },
+                    "tags": {"type": "array"},
+                },
+                "patternProperties": {"^x-": {"type": "boolean"}},
+                "additionalProperties": {"type": ["string", "null"]},
+            }),
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    fn pet_schema() -> Arc<Schema> {
+        Schema::from_serde_json(
+            DraftVersion::OpenApi30,
+            &json!({
+                "components": {"schemas": {"Cat": {"properties": {"lives": {"type": "integer"}}}}},
+                "properties": {"pet": {"discriminator": {"propertyName": "kind", "mapping": {"cat": "#/components/schemas/Cat"}}}},
+            }),
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    fn sorted(mut validation_errors: Vec<ValidationError>) -> Vec<ValidationError> {
+        validation_errors.sort_by(|left, right| (left.path(), left.message()).cmp(&(right.path(), right.message())));
+        validation_errors
+    }
+
+    #[test_case(&person_schema(), &json!({"name": "n", "address": {"street": "s", "number": 1}, "tags": [1, "a"], "x-flag": true, "note": null}))]
+    #[test_case(&person_schema(), &json!({"name": 1, "address": {"street": 1, "number": "1", "floor": 2}, "tags": {}, "x-flag": 1, "note": 1}))]
+    #[test_case(&person_schema(), &json!([{"name": 1}]))]
+    #[test_case(&person_schema(), &json!(1.5))]
+    #[test_case(&pet_schema(), &json!({"pet": {"kind": "cat", "lives": 9}}))]
+    #[test_case(&pet_schema(), &json!({"pet": {"kind": "cat", "lives": "nine"}}))]
+    #[test_case(&pet_schema(), &json!({"pet": {"kind": "dog"}}))]
+    fn streaming_reports_the_tree_validation_errors(schema: &Schema, instance: &Value) {
+        let streaming_validation_errors = schema.json_reader_validation_errors("#", instance.to_string().as_bytes()).collect::<Vec<_>>();
+        assert_eq!(sorted(streaming_validation_errors), sorted(schema.validation_errors("#", instance).collect()));
+    }
+
+    #[test_case(r#"{"name": "n", "name": 1}"# => vec![ValidationError::new("#/name", KeywordType::Unknown, "Duplicated member")])]
+    #[test_case(r#"{"address": {"street": 1, "street": "s"}}"# => vec![
+        ValidationError::new("#/address/street", KeywordType::Type, "Invalid Type"),
+        ValidationError::new("#/address/street", KeywordType::Unknown, "Duplicated member"),
+    ])]
+    fn duplicated_members_are_reported(document: &str) -> Vec<ValidationError> {
+        person_schema().json_reader_validation_errors("#", document.as_bytes()).collect()
+    }
+
+    #[test_case(r#"{"name": "n""#)]
+    #[test_case(r#"{"name": "n"} {}"#)]
+    fn invalid_json_is_reported(document: &str) {
+        let validation_errors = person_schema().json_reader_validation_errors("#", document.as_bytes()).collect::<Vec<_>>();
+        assert!(matches!(validation_errors.as_slice(), [validation_error] if validation_error.message().starts_with("Invalid JSON: ")));
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(feature = "cbor")]
+pub(in crate) mod cbor_;
+#[cfg(feature = "msgpack")]
+pub(in crate) mod msgpack_;
+#[cfg(feature = "serde_json")]
+pub(in crate) mod json_stream_;
+#[cfg(feature = "serde_json")]
+pub(in crate) mod ndjson_;
+#[cfg(feature = "serde_json")]
+pub(in crate) mod serde_json_;
+#[cfg(feature = "yaml")]
+pub(in crate) mod yaml_;
+
+use crate::types::{draft_version::DraftVersion, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder};
+use json_trait_rs::JsonType;
+use loader_rs::LoaderTrait;
+use std::sync::Arc;
+
+// Compile a schema on its own (references are resolved via loader) and initialise it, so it is ready for validation
+pub(in crate) fn compile_schema<T, L>(draft_version: DraftVersion, loader: L, raw_schema: T) -> Result<Arc<Schema>, SchemaError>
+where
+    T: 'static + JsonType,
+    L: 'static + LoaderTrait<T>,
+{
+    compile_schema_with(ScopeBuilder::create(draft_version, loader), raw_schema)
+}
+
+// Same as compile_schema, via a preconfigured scope builder
+pub(in crate) fn compile_schema_with<T>(mut scope_builder: ScopeBuilder<T>, raw_schema: T) -> Result<Arc<Schema>, SchemaError>
+where
+    T: 'static + JsonType,
+{
+    let raw_schema = Arc::new(raw_schema);
+    // The schema is injected into the loader to resolve references within the schema itself
+    let generated_url = scope_builder.inject_schema(&raw_schema);
+    let schema = scope_builder.schema(&generated_url, &*raw_schema)?;
+    let _ = scope_builder.build();
+    Ok(schema)
+}
@@ -0,0 +1,93 @@
+// JsonType cannot be implemented for rmpv::Value (JsonType::as_array and JsonType::as_object have to return
+// references to the same type), so MessagePack values are converted into RustType before validation according to the following rules:
+//  * nil, booleans, integers, floats, strings and arrays map to their JSON counterpart
+//  * maps are objects, keys have to be strings and have to be unique
+//  * binaries, extension types, non finite floats and strings that are not valid UTF-8 have no JSON equivalent
+//    and are reported as validation errors
+use crate::types::{keyword_type::KeywordType, schema::Schema, validation_error::ValidationError, validator_error_iterator::ValidationErrorIterator};
+use json_trait_rs::RustType;
+use rmpv::Value;
+use std::collections::HashMap;
+
+fn number_to_rust_type(path: &str, number: f64) -> Result<RustType, (String, &'static str)> {
+    if number.is_finite() {
+        Ok(RustType::Number(number))
+    } else {
+        Err((path.to_string(), "Float is not finite"))
+    }
+}
+
+// Error is reported as (instance location, reason)
+fn msgpack_to_rust_type(path: &str, value: &Value) -> Result<RustType, (String, &'static str)> {
+    match value {
+        Value::Nil => Ok(RustType::Null),
+        Value::Boolean(boolean) => Ok(RustType::Boolean(*boolean)),
+        Value::Integer(integer) => integer
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| integer.as_u64().map(i128::from))
+            .map(RustType::Integer)
+            .ok_or_else(|| (path.to_string(), "Integer is out of range")),
+        Value::F32(number) => number_to_rust_type(path, f64::from(*number)),
+        Value::F64(number) => number_to_rust_type(path, *number),
+        Value::String(string) => string
+            .as_str()
+            .map(|string| RustType::String(string.to_string()))
+            .ok_or_else(|| (path.to_string(), "String is not valid UTF-8")),
+        Value::Binary(_) => Err((path.to_string(), "Binary has no JSON equivalent")),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| msgpack_to_rust_type(&format!("{}/{}", path, index), item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(RustType::List),
+        Value::Map(items) => {
+            let mut object = HashMap::with_capacity(items.len());
+            for (key, item) in items {
+                let key = key.as_str().ok_or_else(|| (path.to_string(), "Map key is not a string"))?;
+                if object.insert(key.to_string(), msgpack_to_rust_type(&format!("{}/{}", path, key), item)?).is_some() {
+                    return Err((path.to_string(), "Map key is duplicated"));
+                }
+            }
+            Ok(RustType::Object(object))
+        }
+        Value::Ext(..) => Err((path.to_string(), "Extension type has no JSON equivalent")),
+    }
+}
+
+impl Schema {
+    /// Parts of value without JSON equivalent are reported as validation errors, as they could not be validated.
+    pub fn msgpack_validation_errors(&self, path: &str, value: &Value) -> ValidationErrorIterator {
+        match msgpack_to_rust_type(path, value) {
+            Ok(rust_type_value) => self.validation_errors(path, &rust_type_value),
+            Err((location, reason)) => ValidationErrorIterator::from(ValidationError::new(&location, KeywordType::Unknown, reason)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{keyword_type::KeywordType, schema::initialised_draft4_schema, validation_error::ValidationError};
+    use json_trait_rs::rust_type;
+    use rmpv::Value;
+    use test_case::test_case;
+
+    fn object(items: Vec<(&str, Value)>) -> Value {
+        Value::Map(items.into_iter().map(|(key, value)| (Value::from(key), value)).collect())
+    }
+
+    #[test_case(object(vec![("id", Value::from(1)), ("name", Value::from("n"))]) => Vec::<ValidationError>::new())]
+    #[test_case(object(vec![("id", Value::from(u64::max_value()))]) => Vec::<ValidationError>::new())]
+    #[test_case(object(vec![("id", Value::from("1"))]) => vec![ValidationError::new("#/id", KeywordType::Type, "Invalid Type")])]
+    #[test_case(object(vec![("name", Value::Binary(vec![1]))]) => vec![ValidationError::new("#/name", KeywordType::Unknown, "Binary has no JSON equivalent")])]
+    #[test_case(object(vec![("name", Value::Ext(1, vec![1]))]) => vec![ValidationError::new("#/name", KeywordType::Unknown, "Extension type has no JSON equivalent")])]
+    #[test_case(object(vec![("id", Value::from(1)), ("id", Value::from(2))]) => vec![ValidationError::new("#", KeywordType::Unknown, "Map key is duplicated")])]
+    #[test_case(Value::Map(vec![(Value::from(1), Value::from("one"))]) => vec![ValidationError::new("#", KeywordType::Unknown, "Map key is not a string")])]
+    #[test_case(Value::Array(vec![Value::F64(f64::INFINITY)]) => vec![ValidationError::new("#/0", KeywordType::Unknown, "Float is not finite")])]
+    fn validate_msgpack_value(value: Value) -> Vec<ValidationError> {
+        initialised_draft4_schema(rust_type!({"properties": {"id": {"type": "integer"}, "name": {"type": "string"}}}))
+            .expect("Schema is supposed to be valid")
+            .msgpack_validation_errors("#", &value)
+            .collect()
+    }
+}
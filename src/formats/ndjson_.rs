@@ -0,0 +1,227 @@
+// Newline-delimited JSON is validated one record at a time, so only the records of the current batch are kept in memory.
+// Blank lines are skipped (but still counted), lines that are not valid UTF-8 or JSON are reported as validation errors.
+use crate::types::{keyword_type::KeywordType, schema::Schema, validation_error::ValidationError};
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, Read},
+    panic, str,
+    sync::Arc,
+    thread,
+};
+
+// Lines handed to each worker at once, it bounds the memory used while validating in parallel
+const LINES_PER_WORKER: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NdjsonRecord {
+    line: usize,
+    validation_errors: Vec<ValidationError>,
+}
+
+impl NdjsonRecord {
+    /// Line numbers start from 1.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub fn validation_errors(&self) -> &[ValidationError] {
+        &self.validation_errors
+    }
+
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.validation_errors.is_empty()
+    }
+}
+
+fn validate_line(schema: &Schema, line: usize, content: &[u8]) -> NdjsonRecord {
+    let validation_errors = match str::from_utf8(content) {
+        Ok(content) => match serde_json::from_str::<Value>(content) {
+            Ok(value) => schema.validation_errors("#", &value).collect(),
+            Err(error) => vec![ValidationError::new("#", KeywordType::Unknown, &format!("Invalid JSON: {}", error))],
+        },
+        Err(error) => vec![ValidationError::new("#", KeywordType::Unknown, &format!("Invalid UTF-8: {}", error))],
+    };
+    NdjsonRecord { line, validation_errors }
+}
+
+/// Iterator over the validation results of the records read from reader, results are reported in the order of the lines.
+pub struct NdjsonValidator<R: Read> {
+    schema: Arc<Schema>,
+    reader: BufReader<R>,
+    workers: usize,
+    last_line: usize,
+    records: VecDeque<NdjsonRecord>,
+    // Reading errors are reported after the records read before them, no line is read afterwards
+    reading_error: Option<io::Error>,
+    is_exhausted: bool,
+}
+
+impl<R: Read> NdjsonValidator<R> {
+    #[must_use]
+    pub fn create(schema: Arc<Schema>, reader: R) -> Self {
+        Self {
+            schema,
+            reader: BufReader::new(reader),
+            workers: 1,
+            last_line: 0,
+            records: VecDeque::new(),
+            reading_error: None,
+            is_exhausted: false,
+        }
+    }
+
+    /// Validate the records on multiple threads, results are still reported in the order of the lines.
+    #[must_use]
+    pub fn use_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    // Lines are read as bytes (instead of via BufRead::lines), so invalid UTF-8 fails only the line containing it
+    fn read_batch(&mut self) -> Vec<(usize, Vec<u8>)> {
+        let batch_size = if self.workers == 1 { 1 } else { self.workers * LINES_PER_WORKER };
+        let mut batch = Vec::with_capacity(batch_size);
+        while !self.is_exhausted && batch.len() < batch_size {
+            let mut content = Vec::new();
+            match self.reader.read_until(b'\n', &mut content) {
+                Ok(0) => self.is_exhausted = true,
+                Ok(_) => {
+                    self.last_line += 1;
+                    if !content.iter().all(u8::is_ascii_whitespace) {
+                        batch.push((self.last_line, content));
+                    }
+                }
+                Err(error) => {
+                    self.reading_error = Some(error);
+                    self.is_exhausted = true;
+                }
+            }
+        }
+        batch
+    }
+
+    fn validate_batch(&mut self, batch: Vec<(usize, Vec<u8>)>) {
+        if self.workers == 1 {
+            let schema = &self.schema;
+            self.records.extend(batch.into_iter().map(|(line, content)| validate_line(schema, line, &content)));
+            return;
+        }
+
+        let chunk_size = (batch.len() + self.workers - 1) / self.workers;
+        let mut batch = batch.into_iter();
+        let handles = (0..self.workers)
+            .map(|_| batch.by_ref().take(chunk_size).collect::<Vec<_>>())
+            .filter(|chunk| !chunk.is_empty())
+            .map(|chunk| {
+                let schema = self.schema.clone();
+                thread::spawn(move || chunk.into_iter().map(|(line, content)| validate_line(&schema, line, &content)).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        // Handles are joined in spawning order, which preserves the order of the lines
+        for handle in handles {
+            match handle.join() {
+                Ok(records) => self.records.extend(records),
+                Err(panic_payload) => panic::resume_unwind(panic_payload),
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for NdjsonValidator<R> {
+    type Item = io::Result<NdjsonRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.records.is_empty() && !self.is_exhausted {
+            let batch = self.read_batch();
+            self.validate_batch(batch);
+        }
+        self.records.pop_front().map(Ok).or_else(|| self.reading_error.take().map(Err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NdjsonRecord, NdjsonValidator};
+    use crate::types::{draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, validation_error::ValidationError};
+    use serde_json::json;
+    use std::{
+        io::{self, Read},
+        sync::Arc,
+    };
+    use test_case::test_case;
+
+    fn name_schema() -> Arc<Schema> {
+        Schema::from_serde_json(DraftVersion::Draft4, &json!({"properties": {"name": {"type": "string"}}})).expect("Schema is supposed to be valid")
+    }
+
+    fn record(line: usize, validation_errors: Vec<ValidationError>) -> NdjsonRecord {
+        NdjsonRecord { line, validation_errors }
+    }
+
+    // Reader failing once its content is consumed
+    struct FailingReader<'a>(&'a [u8]);
+
+    impl Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                Err(io::Error::new(io::ErrorKind::Other, "broken pipe"))
+            } else {
+                self.0.read(buf)
+            }
+        }
+    }
+
+    #[test_case(1)]
+    #[test_case(4)]
+    fn validate_ndjson(workers: usize) {
+        let content = "{\"name\": \"n\"}\n\n{\"name\": 1}\n{\"name\"\n";
+        let records = NdjsonValidator::create(name_schema(), content.as_bytes())
+            .use_workers(workers)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], record(1, vec![]));
+        assert_eq!(records[1], record(3, vec![ValidationError::new("#/name", KeywordType::Type, "Invalid Type")]));
+        assert_eq!(records[2].line(), 4);
+        assert!(matches!(records[2].validation_errors(), [validation_error] if validation_error.message().starts_with("Invalid JSON: ")));
+    }
+
+    #[test_case(1)]
+    #[test_case(4)]
+    fn validate_ndjson_with_invalid_utf8(workers: usize) {
+        let content: &[u8] = b"{\"name\": \"\xff\"}\r\n{\"name\": \"n\"}\r\n";
+        let records = NdjsonValidator::create(name_schema(), content)
+            .use_workers(workers)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].line(), 1);
+        assert!(matches!(records[0].validation_errors(), [validation_error] if validation_error.message().starts_with("Invalid UTF-8: ")));
+        assert_eq!(records[1], record(2, vec![]));
+    }
+
+    #[test]
+    fn validate_ndjson_in_parallel_preserves_line_order() {
+        let content = (0..2000)
+            .map(|index| format!("{{\"name\": {}}}\n", if index % 3 == 0 { "1" } else { "\"n\"" }))
+            .collect::<String>();
+        let records = NdjsonValidator::create(name_schema(), content.as_bytes())
+            .use_workers(3)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.iter().map(NdjsonRecord::line).collect::<Vec<_>>(), (1..=2000).collect::<Vec<_>>());
+        assert!(records.iter().all(|record| record.is_valid() == ((record.line() - 1) % 3 != 0)));
+    }
+
+    #[test]
+    fn validate_ndjson_reports_reading_errors_after_the_read_records() {
+        let mut validator = NdjsonValidator::create(name_schema(), FailingReader(b"{\"name\": \"n\"}\n"));
+        assert_eq!(validator.next().unwrap().unwrap(), record(1, vec![]));
+        assert_eq!(validator.next().unwrap().unwrap_err().to_string(), "broken pipe");
+        assert!(validator.next().is_none());
+    }
+}
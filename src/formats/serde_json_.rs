@@ -0,0 +1,64 @@
+// JsonType is implemented for serde_json::Value by json-trait-rs (`serde_json` feature), so values are validated
+// in place without any conversion. This module provides only the constructors to work with serde_json directly.
+use crate::{
+    formats::compile_schema,
+    types::{draft_version::DraftVersion, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder},
+};
+use loader_rs::loaders::SerdeJsonLoader;
+use serde_json::Value;
+use std::sync::Arc;
+
+impl ScopeBuilder<Value> {
+    #[must_use]
+    pub fn create_for_serde_json(draft_version: DraftVersion) -> Self {
+        Self::create(draft_version, SerdeJsonLoader::default())
+    }
+}
+
+impl Schema {
+    /// # Errors
+    ///
+    /// The schema is malformed.
+    pub fn from_serde_json(draft_version: DraftVersion, raw_schema: &Value) -> Result<Arc<Self>, SchemaError> {
+        compile_schema(draft_version, SerdeJsonLoader::default(), raw_schema.clone())
+    }
+
+    /// # Errors
+    ///
+    /// The document is not valid JSON or the schema is malformed.
+    pub fn from_json_str(draft_version: DraftVersion, raw_schema: &str) -> Result<Arc<Self>, SchemaError> {
+        let raw_schema: Value = serde_json::from_str(raw_schema).map_err(|error| SchemaError::Parse(error.to_string()))?;
+        compile_schema(draft_version, SerdeJsonLoader::default(), raw_schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, validation_error::ValidationError};
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test_case(&json!({"name": "n"}) => Vec::<ValidationError>::new())]
+    #[test_case(&json!({"name": 1}) => vec![ValidationError::new("#/name", KeywordType::Type, "Invalid Type")])]
+    fn validate_serde_json_value(value: &serde_json::Value) -> Vec<ValidationError> {
+        let schema = Schema::from_serde_json(
+            DraftVersion::Draft4,
+            &json!({"definitions": {"name": {"type": "string"}}, "properties": {"name": {"$ref": "#/definitions/name"}}}),
+        )
+        .expect("Schema is supposed to be valid");
+        schema.validation_errors("#", value).collect()
+    }
+
+    #[test]
+    fn compile_from_str() {
+        let schema = Schema::from_json_str(DraftVersion::Draft4, r#"{"type": "integer"}"#).expect("Schema is supposed to be valid");
+        assert!(schema.is_valid("#", &json!(1)));
+        assert!(!schema.is_valid("#", &json!("1")));
+    }
+
+    #[test_case(r#"{"type": "integer""#)]
+    #[test_case("not json")]
+    fn compile_from_invalid_str(raw_schema: &str) {
+        assert!(matches!(Schema::from_json_str(DraftVersion::Draft4, raw_schema), Err(SchemaError::Parse(_))));
+    }
+}
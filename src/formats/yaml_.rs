@@ -0,0 +1,150 @@
+// JsonType is implemented for serde_yaml::Value by json-trait-rs (`serde_yaml` feature).
+// YAML is a superset of JSON, so values that JSON cannot represent are reported explicitly:
+//  * mapping keys that are not strings (ie. `1: value` or `[a, b]: value`)
+//  * numbers that are not finite (`.inf`, `.nan`)
+// Aliases are expanded while parsing, recursive aliases are reported by serde_yaml as parsing errors.
+use crate::{
+    formats::compile_schema_with,
+    types::{
+        draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError,
+        validator_error_iterator::ValidationErrorIterator,
+    },
+};
+use loader_rs::loaders::SerdeYamlLoader;
+use serde_yaml::Value;
+use std::sync::Arc;
+use url::Url;
+
+// First part of value that has no JSON equivalent, as (instance location, reason)
+fn non_json_value(path: &str, value: &Value) -> Option<(String, String)> {
+    match value {
+        Value::Mapping(mapping) => mapping.iter().find_map(|(key, item)| {
+            if let Value::String(key) = key {
+                non_json_value(&format!("{}/{}", path, key), item)
+            } else {
+                Some((path.to_string(), "Mapping key is not a string".to_string()))
+            }
+        }),
+        Value::Sequence(items) => items.iter().enumerate().find_map(|(index, item)| non_json_value(&format!("{}/{}", path, index), item)),
+        Value::Number(number) if number.as_f64().map_or(false, |number| !number.is_finite()) => Some((path.to_string(), "Number is not finite".to_string())),
+        _ => None,
+    }
+}
+
+fn check_json_compatible(path: &str, raw_schema: &Value) -> Result<(), SchemaError> {
+    if let Some((location, reason)) = non_json_value(path, raw_schema) {
+        Err(SchemaError::Parse(format!("{} (location={})", reason, location)))
+    } else {
+        Ok(())
+    }
+}
+
+// Referenced schemas are checked as retrieved, so values without JSON equivalent are reported also if they are in other documents
+fn check_retrieved_json_compatible(path: &Url, raw_schema: &Value) -> Result<(), SchemaError> {
+    let mut document_uri = path.clone();
+    document_uri.set_fragment(None);
+    check_json_compatible(&format!("{}#{}", document_uri, path.fragment().unwrap_or("")), raw_schema)
+}
+
+impl ScopeBuilder<Value> {
+    /// References (ie. to `.yaml` files) are retrieved and parsed via `SerdeYamlLoader`,
+    /// referenced schemas containing values without JSON equivalent are reported as `SchemaError::Parse`.
+    #[must_use]
+    pub fn create_for_yaml(draft_version: DraftVersion) -> Self {
+        let mut scope_builder = Self::create(draft_version, SerdeYamlLoader::default());
+        let _ = scope_builder.use_raw_schema_check(check_retrieved_json_compatible);
+        scope_builder
+    }
+
+    /// Same as `schema`, for schemas that could contain values without JSON equivalent.
+    ///
+    /// # Errors
+    ///
+    /// The schema is malformed, or it contains values without JSON equivalent (ie. non string keys).
+    pub fn yaml_schema(&mut self, path: &Url, raw_schema: &Value) -> Result<Arc<Schema>, SchemaError> {
+        check_json_compatible("#", raw_schema)?;
+        self.schema(path, raw_schema)
+    }
+}
+
+impl Schema {
+    /// # Errors
+    ///
+    /// The schema is malformed, or it contains values without JSON equivalent (ie. non string keys).
+    pub fn from_yaml(draft_version: DraftVersion, raw_schema: &Value) -> Result<Arc<Self>, SchemaError> {
+        check_json_compatible("#", raw_schema)?;
+        compile_schema_with(ScopeBuilder::create_for_yaml(draft_version), raw_schema.clone())
+    }
+
+    /// # Errors
+    ///
+    /// The document is not valid YAML or the schema is malformed.
+    pub fn from_yaml_str(draft_version: DraftVersion, raw_schema: &str) -> Result<Arc<Self>, SchemaError> {
+        let raw_schema: Value = serde_yaml::from_str(raw_schema).map_err(|error| SchemaError::Parse(error.to_string()))?;
+        Self::from_yaml(draft_version, &raw_schema)
+    }
+
+    /// Parts of value without JSON equivalent are reported as validation errors, as they could not be validated.
+    pub fn yaml_validation_errors(&self, path: &str, value: &Value) -> ValidationErrorIterator {
+        if let Some((location, reason)) = non_json_value(path, value) {
+            ValidationErrorIterator::from(ValidationError::new(&location, KeywordType::Unknown, &reason))
+        } else {
+            self.validation_errors(path, value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{
+        draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError,
+    };
+    use std::path::Path;
+    use test_case::test_case;
+    use url::Url;
+
+    fn server_schema() -> std::sync::Arc<Schema> {
+        let definitions_url = Url::from_file_path(Path::new(env!("CARGO_MANIFEST_DIR")).join("test-data").join("yaml").join("definitions.yaml")).unwrap();
+        Schema::from_yaml_str(
+            DraftVersion::Draft4,
+            &format!(
+                "properties:\n  host:\n    $ref: '{0}#/definitions/host'\n  port:\n    $ref: '{0}#/definitions/port'\n",
+                definitions_url
+            ),
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    #[test_case("host: localhost\nport: 8080\n" => Vec::<ValidationError>::new())]
+    #[test_case("host: localhost\nport: http\n" => vec![ValidationError::new("#/port", KeywordType::Type, "Invalid Type")])]
+    #[test_case("host: localhost\n1: one\n" => vec![ValidationError::new("#", KeywordType::Unknown, "Mapping key is not a string")])]
+    #[test_case("host: localhost\nport: .inf\n" => vec![ValidationError::new("#/port", KeywordType::Unknown, "Number is not finite")])]
+    fn validate_yaml_instance(instance: &str) -> Vec<ValidationError> {
+        server_schema().yaml_validation_errors("#", &serde_yaml::from_str(instance).unwrap()).collect()
+    }
+
+    #[test_case("type: [string, integer\n")]
+    #[test_case("properties:\n  ? [a, b]\n  : {}\n")]
+    fn compile_from_invalid_yaml(raw_schema: &str) {
+        assert!(matches!(Schema::from_yaml_str(DraftVersion::Draft4, raw_schema), Err(SchemaError::Parse(_))));
+    }
+
+    #[test]
+    fn compile_schema_referencing_non_json_schema() {
+        let non_json_url = Url::from_file_path(Path::new(env!("CARGO_MANIFEST_DIR")).join("test-data").join("yaml").join("non-json-definitions.yaml")).unwrap();
+        assert!(matches!(
+            Schema::from_yaml_str(DraftVersion::Draft4, &format!("properties:\n  port:\n    $ref: '{}#/definitions/port'\n", non_json_url)),
+            Err(SchemaError::Parse(detail)) if detail.starts_with("Mapping key is not a string")
+        ));
+    }
+
+    #[test]
+    fn compile_non_json_schema_via_scope_builder() {
+        let mut scope_builder = ScopeBuilder::create_for_yaml(DraftVersion::Draft4);
+        let raw_schema = serde_yaml::from_str("properties:\n  ? [a, b]\n  : {}\n").unwrap();
+        assert!(matches!(
+            scope_builder.yaml_schema(&Url::parse("memory://schema").unwrap(), &raw_schema),
+            Err(SchemaError::Parse(_))
+        ));
+    }
+}
@@ -1,15 +1,10 @@
-use std::{
-    ops::{Generator, GeneratorState},
-    pin::Pin,
-};
-
 #[allow(dead_code)] // GeneratorIterator not yet used in the codebase (other than tests)
 #[allow(missing_debug_implementations)] // No debug implementation to avoid to consume the generator
-pub(in crate) struct GeneratorIterator<Y>(Box<dyn Generator<Yield = Y, Return = ()>>);
+pub(in crate) struct GeneratorIterator<Y>(Box<dyn FnMut() -> Option<Y>>);
 
 impl<G, Y> From<G> for GeneratorIterator<Y>
 where
-    G: 'static + Generator<Yield = Y, Return = ()>,
+    G: 'static + FnMut() -> Option<Y>,
 {
     fn from(generator: G) -> Self {
         Self(Box::new(generator))
@@ -20,17 +15,7 @@ impl<Y> Iterator for GeneratorIterator<Y> {
     type Item = Y;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let pinned_generator = {
-            #[allow(unsafe_code)]
-            unsafe {
-                Pin::new_unchecked(&mut *self.0)
-            }
-        };
-        if let GeneratorState::Yielded(value) = pinned_generator.resume(()) {
-            Some(value)
-        } else {
-            None
-        }
+        (self.0)()
     }
 }
 
@@ -38,23 +23,27 @@ impl<Y> Iterator for GeneratorIterator<Y> {
 mod tests {
     use super::GeneratorIterator;
 
+    fn three_to_five_generator() -> impl FnMut() -> Option<u32> {
+        let mut next_value = 3;
+        move || {
+            if next_value > 5 {
+                None
+            } else {
+                next_value += 1;
+                Some(next_value - 1)
+            }
+        }
+    }
+
     #[test]
     fn generator_is_converted_to_iterator() {
-        let iterator: Box<dyn Iterator<Item = u32>> = Box::new(GeneratorIterator::from(|| {
-            yield 3;
-            yield 4;
-            yield 5;
-        }));
+        let iterator: Box<dyn Iterator<Item = u32>> = Box::new(GeneratorIterator::from(three_to_five_generator()));
         assert_eq!(iterator.collect::<Vec<_>>(), vec![3, 4, 5]);
     }
 
     #[test]
     fn generator_behaves_as_iterator() {
-        let mut iterator = GeneratorIterator::from(|| {
-            yield 3;
-            yield 4;
-            yield 5;
-        });
+        let mut iterator = GeneratorIterator::from(three_to_five_generator());
         assert_eq!(iterator.next(), Some(3));
         assert_eq!(iterator.collect::<Vec<_>>(), vec![4, 5]);
     }
@@ -1,2 +1,4 @@
 pub(in crate) mod empty_iterator;
 pub(in crate) mod generator_iterator;
+#[cfg(feature = "rayon")]
+pub(in crate) mod parallel;
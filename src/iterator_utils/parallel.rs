@@ -0,0 +1,43 @@
+use crate::types::{validation_error::ValidationError, validator_error_iterator::ValidationErrorIterator};
+use rayon::prelude::*;
+
+// Minimum number of items (ie. properties of an object) worth splitting across the rayon thread pool.
+// Smaller inputs are validated on the current thread, as the scheduling overhead would dominate.
+pub(in crate) const PARALLEL_THRESHOLD: usize = 64;
+
+// Validation errors of all the items, reported in the order of the items
+pub(in crate) fn flat_map_validation_errors<I, F>(items: Vec<I>, validate: F) -> ValidationErrorIterator
+where
+    I: Send,
+    F: Fn(I) -> Vec<ValidationError> + Send + Sync,
+{
+    if items.len() < PARALLEL_THRESHOLD {
+        ValidationErrorIterator::new(items.into_iter().flat_map(validate).collect::<Vec<_>>())
+    } else {
+        ValidationErrorIterator::new(items.into_par_iter().flat_map(validate).collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flat_map_validation_errors, PARALLEL_THRESHOLD};
+    use crate::types::{keyword_type::KeywordType, validation_error::ValidationError};
+    use test_case::test_case;
+
+    #[test_case(PARALLEL_THRESHOLD - 1 ; "sequential")]
+    #[test_case(PARALLEL_THRESHOLD * 10 ; "parallel")]
+    fn flat_map_validation_errors_preserves_the_order(items: usize) {
+        let expected_paths = (0..items).filter(|item| item % 2 == 0).map(|item| format!("#/{}", item)).collect::<Vec<_>>();
+        let validation_errors = flat_map_validation_errors((0..items).collect(), |item| {
+            if item % 2 == 0 {
+                vec![ValidationError::new(&format!("#/{}", item), KeywordType::Unknown, "Even")]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(
+            validation_errors.map(|validation_error| validation_error.path().to_string()).collect::<Vec<_>>(),
+            expected_paths
+        );
+    }
+}
@@ -0,0 +1,244 @@
+#[cfg(feature = "rayon")]
+use crate::iterator_utils::parallel::flat_map_validation_errors;
+use crate::{
+    iterator_utils::empty_iterator::EmptyIterator,
+    keywords::pattern_properties_::compile_pattern,
+    types::{
+        annotation::Annotation, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError,
+        validator::Validator, validator_error_iterator::ValidationErrorIterator,
+    },
+};
+use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType, RustType};
+use regex::Regex;
+use std::{any::Any, collections::HashSet, fmt::Debug, sync::Arc};
+use url::Url;
+
+#[derive(Debug)]
+pub(in crate) struct AdditionalProperties {
+    // Properties declared by the sibling `properties` and `patternProperties` keywords are not additional
+    pub(in crate) properties: HashSet<String>,
+    pub(in crate) patterns: Vec<Regex>,
+    // None if additional properties are not allowed at all (`additionalProperties: false`)
+    pub(in crate) schema: Option<Arc<Schema>>,
+}
+
+fn into_str<'l, S: Into<&'l str>>(value: S) -> &'l str {
+    value.into()
+}
+
+fn additional_properties_path(schema_path: &Url) -> Url {
+    let mut result = schema_path.clone();
+    result.set_fragment(Some(&format!("{}/additionalProperties", schema_path.fragment().unwrap_or("").trim_end_matches('/'))));
+    result
+}
+
+impl AdditionalProperties {
+    pub(in crate) fn is_additional(&self, property_name: &str) -> bool {
+        !self.properties.contains(property_name) && !self.patterns.iter().any(|regex| regex.is_match(property_name))
+    }
+}
+
+impl Validator for AdditionalProperties {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        let additional_properties_attribute = if let Some(value) = raw_schema.get_attribute("additionalProperties") {
+            value
+        } else {
+            // additionalProperties attribute is not there so we're done here
+            return Ok(None);
+        };
+
+        let schema = match additional_properties_attribute.as_boolean() {
+            // Additional properties are allowed by default, so there is nothing to validate
+            Some(true) => return Ok(None),
+            Some(false) => None,
+            None if additional_properties_attribute.is_object() => Some(scope_builder.schema(&additional_properties_path(path), additional_properties_attribute)?),
+            None => {
+                return Err(SchemaError::Malformed {
+                    path: path.clone(),
+                    keyword: KeywordType::AdditionalProperties,
+                    detail: format!(
+                        "Value has to be of `{}` or `{}` type. {:?} has type `{}`.",
+                        into_str(PrimitiveType::Boolean),
+                        into_str(PrimitiveType::Object),
+                        additional_properties_attribute,
+                        into_str(additional_properties_attribute.primitive_type())
+                    ),
+                })
+            }
+        };
+
+        // Malformed properties and patternProperties are reported by their own validators
+        let properties = raw_schema
+            .get_attribute("properties")
+            .and_then(|value| value.as_object())
+            .map_or_else(HashSet::new, |properties| properties.items().map(|(property_name, _)| property_name.to_string()).collect());
+        let mut patterns = Vec::new();
+        if let Some(pattern_properties) = raw_schema.get_attribute("patternProperties").and_then(|value| value.as_object()) {
+            for (pattern, _) in pattern_properties.items() {
+                patterns.push(compile_pattern(path, KeywordType::AdditionalProperties, pattern)?);
+            }
+        }
+
+        Ok(Some(Self { properties, patterns, schema }))
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::AdditionalProperties
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if let Some(object) = value.as_object() {
+            ValidationErrorIterator::new(
+                object
+                    .items()
+                    .filter(|(property_name, _)| self.is_additional(property_name))
+                    .flat_map(|(property_name, property_value)| {
+                        let property_path = format!("{}/{}", path, property_name);
+                        if let Some(schema) = &self.schema {
+                            schema.validation_errors(&property_path, property_value)
+                        } else {
+                            ValidationErrorIterator::from(ValidationError::new(
+                                &property_path,
+                                KeywordType::AdditionalProperties,
+                                "Additional property is not allowed",
+                            ))
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            ValidationErrorIterator::new(EmptyIterator::new())
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if let Some(object) = value.as_object() {
+            let additional_properties = object.items().filter(|(property_name, _)| self.is_additional(property_name)).collect();
+            flat_map_validation_errors(additional_properties, |(property_name, property_value)| {
+                let property_path = format!("{}/{}", path, property_name);
+                if let Some(schema) = &self.schema {
+                    schema.par_validation_errors(&property_path, property_value).collect()
+                } else {
+                    vec![ValidationError::new(
+                        &property_path,
+                        KeywordType::AdditionalProperties,
+                        "Additional property is not allowed",
+                    )]
+                }
+            })
+        } else {
+            ValidationErrorIterator::new(EmptyIterator::new())
+        }
+    }
+
+    // Additional properties are the ones no other schema applies to, so their coercion is never ambiguous
+    fn coerce(&self, value: &mut RustType) {
+        if let (RustType::Object(object), Some(schema)) = (value, &self.schema) {
+            object
+                .iter_mut()
+                .filter(|(property_name, _)| self.is_additional(property_name))
+                .for_each(|(_, property_value)| schema.coerce(property_value));
+        }
+    }
+
+    fn sanitise(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            if let Some(schema) = &self.schema {
+                object
+                    .iter_mut()
+                    .filter(|(property_name, _)| self.is_additional(property_name))
+                    .for_each(|(_, property_value)| schema.sanitise(property_value));
+            } else {
+                object.retain(|property_name, _| !self.is_additional(property_name));
+            }
+        }
+    }
+
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        value.as_object().map_or(true, |object| {
+            object
+                .items()
+                .filter(|(property_name, _)| self.is_additional(property_name))
+                .all(|(property_name, property_value)| match &self.schema {
+                    Some(schema) => schema.collect_annotations(&format!("{}/{}", path, property_name), property_value, annotations),
+                    None => false,
+                })
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use crate::types::{
+        keyword_type::KeywordType,
+        schema::{draft4_schema, initialised_draft4_schema, Schema},
+        schema_error::SchemaError,
+        validation_error::ValidationError,
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+
+    // Testing constants
+    fn no_additional_properties_schema() -> Schema {
+        draft4_schema(rust_type!({"properties": {"name": {}}, "patternProperties": {"^x-": {}}, "additionalProperties": false})).expect("Schema is supposed to be valid")
+    }
+
+    fn boolean_additional_properties_schema() -> Schema {
+        draft4_schema(rust_type!({"properties": {"name": {}}, "additionalProperties": {"type": "boolean"}})).expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"additionalProperties": true}) => false)]
+    #[test_case(rust_type!({"additionalProperties": false}) => true)]
+    #[test_case(rust_type!({"additionalProperties": {}}) => true)]
+    fn build_additional_properties_object_valid(raw_schema: RustType) -> bool {
+        let schema = draft4_schema(raw_schema).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::AdditionalProperties, &|maybe_draft_validator| maybe_draft_validator.is_some())
+    }
+
+    #[test_case(rust_type!({"additionalProperties": 1}), "Value has to be of `boolean` or `object` type. Integer(1) has type `integer`.")]
+    fn build_additional_properties_object_invalid(raw_schema: RustType, expected_malformed_error_detail: &str) {
+        assert!(matches!(
+            draft4_schema(raw_schema),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::AdditionalProperties,
+                detail,
+                ..
+            }) if detail == expected_malformed_error_detail
+        ));
+    }
+
+    #[test_case(&no_additional_properties_schema(), &rust_type!([1, 2]), &[])]
+    #[test_case(&no_additional_properties_schema(), &rust_type!({"name": 1, "x-id": 2}), &[])]
+    #[test_case(&no_additional_properties_schema(), &rust_type!({"name": 1, "id": 2}), &[
+        ValidationError::new("#/id", KeywordType::AdditionalProperties, "Additional property is not allowed"),
+    ])]
+    #[test_case(&boolean_additional_properties_schema(), &rust_type!({"name": 1, "active": true}), &[])]
+    #[test_case(&boolean_additional_properties_schema(), &rust_type!({"name": 1, "active": 1}), &[ValidationError::new("#/active", KeywordType::Type, "Invalid Type")])]
+    fn validate(schema: &Schema, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        schema.do_on_validator(KeywordType::AdditionalProperties, &|maybe_draft_validator| {
+            let validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+        });
+    }
+
+    #[test_case(&rust_type!({"name": "1", "count": "2", "x-id": "3"}) => rust_type!({"name": "1", "count": 2, "x-id": "3"}))]
+    #[test_case(&rust_type!({"count": "two"}) => rust_type!({"count": "two"}))]
+    fn coerce(value: &RustType) -> RustType {
+        let schema = initialised_draft4_schema(rust_type!({
+            "properties": {"name": {"type": "string"}},
+            "patternProperties": {"^x-": {}},
+            "additionalProperties": {"type": "integer"},
+        }))
+        .expect("Schema is supposed to be valid");
+        schema.validate_with_coercion("#", value).0
+    }
+}
@@ -0,0 +1,178 @@
+use crate::types::{
+    annotation::Annotation, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator,
+    validator_error_iterator::ValidationErrorIterator,
+};
+use json_trait_rs::{JsonType, PrimitiveType, RustType};
+use std::{any::Any, fmt::Debug, sync::Arc};
+use url::Url;
+
+#[derive(Debug)]
+pub(in crate) struct AllOf {
+    pub(in crate) schemas: Vec<Arc<Schema>>,
+}
+
+fn into_str<'l, S: Into<&'l str>>(value: S) -> &'l str {
+    value.into()
+}
+
+fn malformed_all_of<J: JsonType>(path: &Url, value: &J) -> SchemaError {
+    SchemaError::Malformed {
+        path: path.clone(),
+        keyword: KeywordType::AllOf,
+        detail: format!(
+            "Values have to be of `{}` type. {:?} has type `{}`.",
+            into_str(PrimitiveType::Object),
+            value,
+            into_str(value.primitive_type())
+        ),
+    }
+}
+
+fn item_path(schema_path: &Url, index: usize) -> Url {
+    let mut result = schema_path.clone();
+    result.set_fragment(Some(&format!("{}/allOf/{}", schema_path.fragment().unwrap_or("").trim_end_matches('/'), index)));
+    result
+}
+
+impl Validator for AllOf {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        let all_of_attribute = if let Some(value) = raw_schema.get_attribute("allOf") {
+            value
+        } else {
+            // allOf attribute is not there so we're done here
+            return Ok(None);
+        };
+        let all_of_items = if let Some(value) = all_of_attribute.as_array() {
+            value
+        } else {
+            return Err(SchemaError::Malformed {
+                path: path.clone(),
+                keyword: KeywordType::AllOf,
+                detail: format!(
+                    "Value has to be of `{}` type. {:?} has type `{}`.",
+                    into_str(PrimitiveType::Array),
+                    all_of_attribute,
+                    into_str(all_of_attribute.primitive_type())
+                ),
+            });
+        };
+
+        let mut schemas = Vec::new();
+        for (index, item) in all_of_items.enumerate() {
+            if item.is_object() {
+                schemas.push(scope_builder.schema(&item_path(path, index), item)?);
+            } else {
+                return Err(malformed_all_of(path, item));
+            }
+        }
+
+        if schemas.is_empty() {
+            Err(SchemaError::Malformed {
+                path: path.clone(),
+                keyword: KeywordType::AllOf,
+                detail: "This array MUST have at least one element.".to_string(),
+            })
+        } else {
+            Ok(Some(Self { schemas }))
+        }
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::AllOf
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        ValidationErrorIterator::new(self.schemas.iter().flat_map(|schema| schema.validation_errors(path, value)).collect::<Vec<_>>())
+    }
+
+    fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
+        self.schemas.iter().all(|schema| schema.is_valid(path, value))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        ValidationErrorIterator::new(self.schemas.iter().flat_map(|schema| schema.par_validation_errors(path, value)).collect::<Vec<_>>())
+    }
+
+    fn apply_defaults(&self, value: &mut RustType) {
+        self.schemas.iter().for_each(|schema| schema.apply_defaults(value));
+    }
+
+    fn coerce(&self, value: &mut RustType) {
+        self.schemas.iter().for_each(|schema| schema.coerce(value));
+    }
+
+    fn sanitise(&self, value: &mut RustType) {
+        self.schemas.iter().for_each(|schema| schema.sanitise(value));
+    }
+
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        self.schemas.iter().all(|schema| schema.collect_annotations(path, value, annotations))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use super::AllOf;
+    use crate::{
+        keywords::DraftValidator,
+        types::{
+            keyword_type::KeywordType,
+            schema::{draft4_schema, Schema},
+            schema_error::SchemaError,
+            validation_error::ValidationError,
+        },
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+
+    // Testing constants
+    fn all_of_schema() -> Schema {
+        draft4_schema(rust_type!({"allOf": [{"type": ["integer", "string"]}, {"type": ["boolean", "integer"]}]})).expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"allOf": [{}]}) => 1)]
+    #[test_case(rust_type!({"allOf": [{"type": "string"}, {"properties": {}}]}) => 2)]
+    fn build_all_of_object_valid(raw_schema: RustType) -> usize {
+        let schema = draft4_schema(raw_schema).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::AllOf, &|maybe_draft_validator| match maybe_draft_validator {
+            Some(DraftValidator::AllOf(AllOf { schemas })) => schemas.len(),
+            _ => panic!("Expected validator to be found and of correct type"),
+        })
+    }
+
+    #[test_case(rust_type!({"allOf": {}}), "Value has to be of `array` type. Object({}) has type `object`.")]
+    #[test_case(rust_type!({"allOf": []}), "This array MUST have at least one element.")]
+    #[test_case(rust_type!({"allOf": [1]}), "Values have to be of `object` type. Integer(1) has type `integer`.")]
+    fn build_all_of_object_invalid(raw_schema: RustType, expected_malformed_error_detail: &str) {
+        assert!(matches!(
+            draft4_schema(raw_schema),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::AllOf,
+                detail,
+                ..
+            }) if detail == expected_malformed_error_detail
+        ));
+    }
+
+    #[test_case(&all_of_schema(), &rust_type!(1), &[])]
+    #[test_case(&all_of_schema(), &rust_type!("text"), &[ValidationError::new("#", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&all_of_schema(), &rust_type!(null), &[
+        ValidationError::new("#", KeywordType::Type, "Invalid Type"),
+        ValidationError::new("#", KeywordType::Type, "Invalid Type"),
+    ])]
+    fn validate(schema: &Schema, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        schema.do_on_validator(KeywordType::AllOf, &|maybe_draft_validator| {
+            let validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+        });
+    }
+}
@@ -0,0 +1,137 @@
+use crate::types::{
+    custom_keyword::{CustomKeyword, CustomKeywordRegistration},
+    instance::Instance,
+    keyword_type::KeywordType,
+    schema_error::SchemaError,
+    validator_error_iterator::ValidationErrorIterator,
+};
+use json_trait_rs::JsonType;
+use url::Url;
+
+#[derive(Debug)]
+pub(in crate) struct Custom {
+    pub(in crate) keyword: &'static str,
+    pub(in crate) custom_keyword: Box<dyn CustomKeyword>,
+}
+
+impl Custom {
+    pub(in crate) fn compile<J: JsonType>(registration: &CustomKeywordRegistration, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError> {
+        let keyword_value = if let Some(value) = raw_schema.get_attribute(registration.keyword) {
+            value
+        } else {
+            // custom keyword is not there so we're done here
+            return Ok(None);
+        };
+
+        Ok((registration.compile)(path, &keyword_value.to_rust_type())?.map(|custom_keyword| Self {
+            keyword: registration.keyword,
+            custom_keyword,
+        }))
+    }
+
+    pub(in crate) const fn keyword_type(&self) -> KeywordType {
+        KeywordType::Custom(self.keyword)
+    }
+
+    pub(in crate) fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        self.custom_keyword.validation_errors(path, Instance::new(value))
+    }
+
+    pub(in crate) fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
+        self.custom_keyword.is_valid(path, Instance::new(value))
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use crate::{
+        iterator_utils::empty_iterator::EmptyIterator,
+        keywords::DraftValidator,
+        types::{
+            custom_keyword::CustomKeyword,
+            draft_version::DraftVersion,
+            instance::Instance,
+            keyword_type::KeywordType,
+            schema::Schema,
+            schema_error::SchemaError,
+            scope_builder::scope_builder_create_and_build,
+            validation_error::ValidationError,
+            validator_error_iterator::ValidationErrorIterator,
+        },
+    };
+    use json_trait_rs::{rust_type, JsonType, RustType};
+    use std::sync::Arc;
+    use test_case::test_case;
+    use url::Url;
+
+    // Testing constants
+    #[derive(Debug)]
+    struct MultipleOf {
+        divisor: i128,
+    }
+
+    impl CustomKeyword for MultipleOf {
+        fn compile(path: &Url, keyword_value: &RustType) -> Result<Option<Self>, SchemaError> {
+            match keyword_value.as_integer() {
+                Some(0) => Ok(None),
+                Some(divisor) => Ok(Some(Self { divisor })),
+                None => Err(SchemaError::Malformed {
+                    path: path.clone(),
+                    keyword: KeywordType::Custom("x-multiple-of"),
+                    detail: "Value has to be an integer".to_string(),
+                }),
+            }
+        }
+
+        fn validation_errors(&self, path: &str, value: Instance<'_>) -> ValidationErrorIterator {
+            match value.as_integer() {
+                Some(integer) if integer % self.divisor != 0 => {
+                    ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Custom("x-multiple-of"), "Not a multiple"))
+                }
+                _ => ValidationErrorIterator::new(EmptyIterator::new()),
+            }
+        }
+    }
+
+    // The root schema is compiled via the scope builder, so it is initialised by ScopeBuilder::build
+    fn draft4_schema_with_multiple_of(raw_schema: RustType) -> Result<Arc<Schema>, SchemaError> {
+        scope_builder_create_and_build(DraftVersion::Draft4, raw_schema, &|scope_builder, generated_url, raw_schema| {
+            let _ = scope_builder.register_custom_keyword::<MultipleOf>("x-multiple-of");
+            scope_builder.schema(generated_url, raw_schema)
+        })
+    }
+
+    fn multiple_of_ten_schema() -> Arc<Schema> {
+        draft4_schema_with_multiple_of(rust_type!({"type": "integer", "x-multiple-of": 10})).expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"x-multiple-of": 10}) => true)]
+    #[test_case(rust_type!({"x-multiple-of": 0}) => false ; "custom keyword compiling to no validator")]
+    #[test_case(rust_type!({"type": "integer"}) => false ; "custom keyword not present")]
+    fn build_custom_keyword_valid(raw_schema: RustType) -> bool {
+        let schema = draft4_schema_with_multiple_of(raw_schema).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::Custom("x-multiple-of"), &|maybe_draft_validator| {
+            matches!(maybe_draft_validator, Some(DraftValidator::Custom(custom)) if custom.keyword == "x-multiple-of")
+        })
+    }
+
+    #[test]
+    fn build_custom_keyword_invalid() {
+        assert!(matches!(
+            draft4_schema_with_multiple_of(rust_type!({"x-multiple-of": "ten"})),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::Custom("x-multiple-of"),
+                detail,
+                ..
+            }) if detail == "Value has to be an integer"
+        ));
+    }
+
+    #[test_case(&multiple_of_ten_schema(), &rust_type!(20), &[])]
+    #[test_case(&multiple_of_ten_schema(), &rust_type!(21), &[ValidationError::new("#", KeywordType::Custom("x-multiple-of"), "Not a multiple")])]
+    #[test_case(&multiple_of_ten_schema(), &rust_type!("text"), &[ValidationError::new("#", KeywordType::Type, "Invalid Type")])]
+    fn validate(schema: &Arc<Schema>, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+}
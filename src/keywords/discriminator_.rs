@@ -0,0 +1,352 @@
+use crate::{
+    iterator_utils::empty_iterator::EmptyIterator,
+    keywords::{
+        ref_::{full_uri, Ref},
+        DraftValidator,
+    },
+    types::{
+        keyword_type::KeywordType,
+        schema::Schema,
+        schema_error::SchemaError,
+        scope_builder::{schema_cache_key, ScopeBuilder},
+        validation_error::ValidationError,
+        validator::Validator,
+        validator_error_iterator::ValidationErrorIterator,
+    },
+};
+use json_trait_rs::{JsonMapTrait, JsonType};
+use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
+use url::Url;
+
+#[derive(Debug)]
+pub(in crate) struct Discriminator {
+    // Path of the schema defining the discriminator
+    pub(in crate) path: Url,
+    pub(in crate) property_name: String,
+    // Explicit mapping on top of the implicit one (see implicit_mapping).
+    // Empty if neither is defined, in such case only the presence of the property is verified
+    pub(in crate) mapping: HashMap<String, Ref>,
+}
+
+// `#/components/schemas/<schema_name>` of the document containing the schema located by path
+fn component_schema_uri(path: &Url, schema_name: &str) -> Url {
+    let mut uri = path.clone();
+    uri.set_fragment(Some(&format!("/components/schemas/{}", schema_name.replace('~', "~0").replace('/', "~1"))));
+    uri
+}
+
+fn is_same_schema(left: &Url, right: &Url) -> bool {
+    schema_cache_key(left) == schema_cache_key(right)
+}
+
+// Schema names, and their location, that the discriminator of the schema located by path could select without explicit mapping:
+//  * the schemas referenced by its `oneOf` and `anyOf`, named after the last segment of their JSON pointer
+//  * the schemas of `#/components/schemas` (of the same document) that extend it via `allOf`, and the schema itself if it is one of them
+// Only those schemas are compiled, so schemas that the discriminator could never select (ie. malformed ones) are not compiled.
+fn implicit_mapping<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Vec<(String, Url)> {
+    let mut mapping = Vec::new();
+    for keyword in &["oneOf", "anyOf"] {
+        for subschema in raw_schema.get_attribute(keyword).and_then(|value| value.as_array()).into_iter().flatten() {
+            if let Some(reference) = subschema.get_attribute("$ref").and_then(|value| value.as_string()) {
+                let mapped_schema_uri = full_uri(path, reference);
+                if let Some(schema_name) = mapped_schema_uri
+                    .fragment()
+                    .and_then(|fragment| fragment.rsplit('/').next())
+                    .filter(|name| !name.is_empty())
+                {
+                    mapping.push((schema_name.replace("~1", "/").replace("~0", "~"), mapped_schema_uri.clone()));
+                }
+            }
+        }
+    }
+
+    let mut document_uri = path.clone();
+    document_uri.set_fragment(None);
+    let document: Arc<T> = if let Ok(document) = scope_builder.retrieve_schema(&document_uri) {
+        document
+    } else {
+        return mapping;
+    };
+    let component_schemas = if let Some(component_schemas) = document
+        .get_attribute("components")
+        .and_then(|components| components.get_attribute("schemas"))
+        .and_then(|schemas| schemas.as_object())
+    {
+        component_schemas
+    } else {
+        return mapping;
+    };
+    for (schema_name, component_schema) in component_schemas.items() {
+        let component_uri = component_schema_uri(path, schema_name);
+        let is_extending = component_schema
+            .get_attribute("allOf")
+            .and_then(|value| value.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|subschema| subschema.get_attribute("$ref").and_then(|value| value.as_string()))
+            .any(|reference| is_same_schema(&full_uri(&component_uri, reference), path));
+        if is_extending || is_same_schema(&component_uri, path) {
+            mapping.push((schema_name.to_string(), component_uri));
+        }
+    }
+    mapping
+}
+
+fn malformed_discriminator(path: &Url, detail: &str) -> SchemaError {
+    SchemaError::Malformed {
+        path: path.clone(),
+        keyword: KeywordType::Discriminator,
+        detail: detail.to_string(),
+    }
+}
+
+impl Discriminator {
+    // Mapped schemas usually extend the schema defining the discriminator (ie. `allOf: [{"$ref": "#/components/schemas/Pet"}]`).
+    // The defining schema is skipped when reached via `$ref` and `allOf`, as it is validated already and
+    // evaluating its discriminator again on the same value would never end.
+    fn mapped_schema_validation_errors<T: 'static + JsonType>(&self, mapped_schema: &Schema, path: &str, value: &T) -> Vec<ValidationError> {
+        if is_same_schema(&mapped_schema.path, &self.path) {
+            return Vec::new();
+        }
+        mapped_schema
+            .validators
+            .iter()
+            .flat_map(|validator| match validator {
+                DraftValidator::Ref(ref_validator) => match ref_validator.referenced_schema() {
+                    Some(referenced_schema) => self.mapped_schema_validation_errors(&referenced_schema, path, value),
+                    None => ref_validator.validation_errors(path, value).collect(),
+                },
+                DraftValidator::AllOf(all_of_validator) => all_of_validator
+                    .schemas
+                    .iter()
+                    .flat_map(|schema| self.mapped_schema_validation_errors(schema, path, value))
+                    .collect(),
+                validator => validator.validation_errors(path, value).collect(),
+            })
+            .collect()
+    }
+}
+
+impl Validator for Discriminator {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        let discriminator_attribute = if let Some(value) = raw_schema.get_attribute("discriminator") {
+            value
+        } else {
+            // discriminator attribute is not there so we're done here
+            return Ok(None);
+        };
+
+        let property_name = if let Some(value) = discriminator_attribute.get_attribute("propertyName").and_then(|value| value.as_string()) {
+            value.to_string()
+        } else {
+            return Err(malformed_discriminator(path, "Discriminator object has to define `propertyName` of `string` type."));
+        };
+
+        let mut mapping = HashMap::new();
+        for (schema_name, mapped_schema_uri) in implicit_mapping(scope_builder, path, raw_schema) {
+            let mapped_schema = Ref::create(scope_builder, mapped_schema_uri)?;
+            let _ = mapping.insert(schema_name, mapped_schema);
+        }
+        if let Some(mapping_attribute) = discriminator_attribute.get_attribute("mapping") {
+            let mapping_object = if let Some(value) = mapping_attribute.as_object() {
+                value
+            } else {
+                return Err(malformed_discriminator(path, "Discriminator `mapping` has to be of `object` type."));
+            };
+            for (discriminator_value, reference) in mapping_object.items() {
+                let reference = if let Some(value) = reference.as_string() {
+                    value
+                } else {
+                    return Err(malformed_discriminator(path, "Discriminator `mapping` values have to be of `string` type."));
+                };
+                // Values that are not references (no `#` nor `/`) are schema names, as for the implicit mapping
+                let mapped_schema_uri = if reference.contains('#') || reference.contains('/') {
+                    full_uri(path, reference)
+                } else {
+                    component_schema_uri(path, reference)
+                };
+                let _ = mapping.insert(discriminator_value.to_string(), Ref::create(scope_builder, mapped_schema_uri)?);
+            }
+        }
+
+        Ok(Some(Self {
+            path: path.clone(),
+            property_name,
+            mapping,
+        }))
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::Discriminator
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        let object = if let Some(object) = value.as_object() {
+            object
+        } else {
+            return ValidationErrorIterator::new(EmptyIterator::new());
+        };
+
+        match object.get_attribute(&self.property_name).map(|property_value| property_value.as_string()) {
+            None => ValidationErrorIterator::from(ValidationError::new(
+                path,
+                KeywordType::Discriminator,
+                &format!("Discriminator property `{}` is missing", self.property_name),
+            )),
+            Some(None) => ValidationErrorIterator::from(ValidationError::new(
+                path,
+                KeywordType::Discriminator,
+                &format!("Discriminator property `{}` has to be a string", self.property_name),
+            )),
+            Some(Some(_)) if self.mapping.is_empty() => ValidationErrorIterator::new(EmptyIterator::new()),
+            Some(Some(discriminator_value)) => {
+                if let Some(mapped_schema) = self.mapping.get(discriminator_value) {
+                    match mapped_schema.referenced_schema() {
+                        Some(referenced_schema) => ValidationErrorIterator::new(self.mapped_schema_validation_errors(&referenced_schema, path, value)),
+                        None => mapped_schema.validation_errors(path, value),
+                    }
+                } else {
+                    ValidationErrorIterator::from(ValidationError::new(
+                        path,
+                        KeywordType::Discriminator,
+                        &format!("Discriminator value `{}` is not mapped", discriminator_value),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use super::Discriminator;
+    use crate::{
+        hash_set,
+        keywords::DraftValidator,
+        types::{
+            keyword_type::KeywordType,
+            schema::{initialised_openapi30_schema, openapi30_schema, Schema},
+            schema_error::SchemaError,
+            validation_error::ValidationError,
+        },
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    // Testing constants
+    fn pet_schema() -> Schema {
+        openapi30_schema(
+            rust_type!({
+                "components": {"schemas": {"Cat": {"properties": {"lives": {"type": "integer"}}}, "Dog": {"properties": {"bark": {"type": "boolean"}}}}},
+                "discriminator": {"propertyName": "kind", "mapping": {"cat": "#/components/schemas/Cat", "dog": "#/components/schemas/Dog"}},
+                "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+            }),
+            None,
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    fn named_pet_schema() -> Schema {
+        openapi30_schema(
+            rust_type!({
+                "components": {"schemas": {"Cat": {"properties": {"lives": {"type": "integer"}}}, "Dog": {"properties": {"bark": {"type": "boolean"}}}}},
+                "discriminator": {"propertyName": "kind", "mapping": {"cat": "Cat"}},
+                "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+            }),
+            None,
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    fn unmapped_pet_schema() -> Schema {
+        openapi30_schema(rust_type!({"discriminator": {"propertyName": "kind"}}), None).expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"discriminator": {"propertyName": "kind"}}), "kind", &hash_set![])]
+    #[test_case(
+        rust_type!({"definitions": {"Cat": {}}, "discriminator": {"propertyName": "kind", "mapping": {"cat": "#/definitions/Cat"}}}),
+        "kind",
+        &hash_set!["cat".to_string()]
+    )]
+    #[test_case(
+        rust_type!({
+            "components": {"schemas": {"Cat": {}, "Broken": {"type": 1}}},
+            "discriminator": {"propertyName": "kind"},
+            "anyOf": [{"$ref": "#/components/schemas/Cat"}],
+        }),
+        "kind",
+        &hash_set!["Cat".to_string()]
+    )]
+    fn build_discriminator_object_valid(raw_schema: RustType, expected_property_name: &str, expected_mapping_keys: &HashSet<String>) {
+        let schema = openapi30_schema(raw_schema, None).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::Discriminator, &|maybe_draft_validator| {
+            let draft_validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert!(matches!(
+                draft_validator,
+                DraftValidator::Discriminator(Discriminator { property_name, mapping, .. })
+                if property_name == expected_property_name && &mapping.keys().cloned().collect::<HashSet<_>>() == expected_mapping_keys
+            ))
+        });
+    }
+
+    #[test_case(rust_type!({"discriminator": {}}), "Discriminator object has to define `propertyName` of `string` type.")]
+    #[test_case(rust_type!({"discriminator": {"propertyName": "kind", "mapping": []}}), "Discriminator `mapping` has to be of `object` type.")]
+    #[test_case(rust_type!({"discriminator": {"propertyName": "kind", "mapping": {"cat": 1}}}), "Discriminator `mapping` values have to be of `string` type.")]
+    fn build_discriminator_object_invalid(raw_schema: RustType, expected_malformed_error_detail: &str) {
+        assert!(matches!(
+            openapi30_schema(raw_schema, None),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::Discriminator,
+                detail,
+                ..
+            }) if detail == expected_malformed_error_detail
+        ));
+    }
+
+    #[test_case(&pet_schema(), &rust_type!("text"), &[])]
+    #[test_case(&pet_schema(), &rust_type!({"kind": "cat", "lives": 9}), &[])]
+    #[test_case(&pet_schema(), &rust_type!({"kind": "cat", "lives": "nine"}), &[ValidationError::new("#/lives", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&pet_schema(), &rust_type!({"kind": "dog", "bark": "loud"}), &[ValidationError::new("#/bark", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&pet_schema(), &rust_type!({"kind": "Cat", "lives": "nine"}), &[ValidationError::new("#/lives", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&pet_schema(), &rust_type!({"kind": "fish"}), &[ValidationError::new("#", KeywordType::Discriminator, "Discriminator value `fish` is not mapped")])]
+    #[test_case(&pet_schema(), &rust_type!({}), &[ValidationError::new("#", KeywordType::Discriminator, "Discriminator property `kind` is missing")])]
+    #[test_case(&named_pet_schema(), &rust_type!({"kind": "cat", "lives": "nine"}), &[ValidationError::new("#/lives", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&named_pet_schema(), &rust_type!({"kind": "Dog", "bark": "loud"}), &[ValidationError::new("#/bark", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&unmapped_pet_schema(), &rust_type!({"kind": "fish"}), &[])]
+    #[test_case(&unmapped_pet_schema(), &rust_type!({"kind": 1}), &[ValidationError::new("#", KeywordType::Discriminator, "Discriminator property `kind` has to be a string")])]
+    fn validate(schema: &Schema, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        schema.do_on_validator(KeywordType::Discriminator, &|maybe_draft_validator| {
+            let validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+        });
+    }
+
+    // Mapped schemas extending the schema that defines the discriminator do not evaluate the discriminator again
+    #[test_case(&rust_type!({"kind": "Cat", "lives": 9}), &[])]
+    #[test_case(&rust_type!({"kind": "Cat", "lives": "nine"}), &[ValidationError::new("#/lives", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&rust_type!({"kind": "Pet"}), &[])]
+    #[test_case(&rust_type!({"kind": "Fish"}), &[ValidationError::new("#", KeywordType::Discriminator, "Discriminator value `Fish` is not mapped")])]
+    fn validate_extending_schemas(object: &RustType, expected_validation_errors: &[ValidationError]) {
+        let schema = initialised_openapi30_schema(
+            rust_type!({
+                "components": {"schemas": {
+                    "Pet": {"discriminator": {"propertyName": "kind"}, "properties": {"kind": {"type": "string"}}},
+                    "Cat": {"allOf": [{"$ref": "#/components/schemas/Pet"}, {"properties": {"lives": {"type": "integer"}}}]},
+                }},
+                "$ref": "#/components/schemas/Pet",
+            }),
+            None,
+        )
+        .expect("Schema is supposed to be valid");
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+}
@@ -1,29 +1,111 @@
+pub(in crate) mod additional_properties_;
+pub(in crate) mod all_of_;
+pub(in crate) mod custom_;
+pub(in crate) mod discriminator_;
+pub(in crate) mod pattern_properties_;
 pub(in crate) mod properties_;
+pub(in crate) mod read_write_only_;
 pub(in crate) mod ref_;
 pub(in crate) mod type_;
 
-#[cfg(test)]
-use crate::types::keyword_type::KeywordType;
 use crate::types::{
-    draft_version::DraftVersion, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator, validator_error_iterator::ValidationErrorIterator,
+    annotation::Annotation, draft_version::DraftVersion, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator,
+    validator_error_iterator::ValidationErrorIterator,
 };
-use json_trait_rs::JsonType;
-use std::{ops::Deref, sync::Arc};
+use json_trait_rs::{JsonType, RustType};
+use std::{collections::HashMap, sync::Arc};
 use url::Url;
 
 #[derive(Debug)]
 pub(in crate) enum DraftValidator {
     Ref(ref_::Ref),
+    AllOf(all_of_::AllOf),
     Properties(properties_::Properties),
+    PatternProperties(pattern_properties_::PatternProperties),
+    AdditionalProperties(additional_properties_::AdditionalProperties),
     Type(type_::Type),
+    Discriminator(discriminator_::Discriminator),
+    ReadOnly(read_write_only_::ReadOnly),
+    WriteOnly(read_write_only_::WriteOnly),
+    Custom(custom_::Custom),
 }
 
 impl DraftValidator {
     pub(in crate) fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
         match self {
             Self::Ref(validator) => validator.validation_errors(path, value),
+            Self::AllOf(validator) => validator.validation_errors(path, value),
             Self::Properties(validator) => validator.validation_errors(path, value),
+            Self::PatternProperties(validator) => validator.validation_errors(path, value),
+            Self::AdditionalProperties(validator) => validator.validation_errors(path, value),
             Self::Type(validator) => validator.validation_errors(path, value),
+            Self::Discriminator(validator) => validator.validation_errors(path, value),
+            Self::ReadOnly(validator) => validator.validation_errors(path, value),
+            Self::WriteOnly(validator) => validator.validation_errors(path, value),
+            Self::Custom(validator) => validator.validation_errors(path, value),
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(in crate) fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        match self {
+            Self::Ref(validator) => validator.par_validation_errors(path, value),
+            Self::AllOf(validator) => validator.par_validation_errors(path, value),
+            Self::Properties(validator) => validator.par_validation_errors(path, value),
+            Self::PatternProperties(validator) => validator.par_validation_errors(path, value),
+            Self::AdditionalProperties(validator) => validator.par_validation_errors(path, value),
+            Self::Type(validator) => validator.par_validation_errors(path, value),
+            Self::Discriminator(validator) => validator.par_validation_errors(path, value),
+            Self::ReadOnly(validator) => validator.par_validation_errors(path, value),
+            Self::WriteOnly(validator) => validator.par_validation_errors(path, value),
+            Self::Custom(validator) => validator.validation_errors(path, value),
+        }
+    }
+
+    pub(in crate) fn apply_defaults(&self, value: &mut RustType) {
+        match self {
+            Self::Ref(validator) => validator.apply_defaults(value),
+            Self::AllOf(validator) => validator.apply_defaults(value),
+            Self::Properties(validator) => validator.apply_defaults(value),
+            // The other keywords do not describe missing properties, so there are no defaults to apply
+            Self::PatternProperties(_) | Self::AdditionalProperties(_) | Self::Type(_) | Self::Discriminator(_) | Self::ReadOnly(_) | Self::WriteOnly(_) | Self::Custom(_) => {}
+        }
+    }
+
+    pub(in crate) fn coerce(&self, value: &mut RustType) {
+        match self {
+            Self::Ref(validator) => validator.coerce(value),
+            Self::AllOf(validator) => validator.coerce(value),
+            Self::Properties(validator) => validator.coerce(value),
+            Self::PatternProperties(validator) => validator.coerce(value),
+            Self::AdditionalProperties(validator) => validator.coerce(value),
+            Self::Type(validator) => validator.coerce(value),
+            // The other keywords do not constrain the type of the value, so there is nothing to coerce
+            Self::Discriminator(_) | Self::ReadOnly(_) | Self::WriteOnly(_) | Self::Custom(_) => {}
+        }
+    }
+
+    pub(in crate) fn sanitise(&self, value: &mut RustType) {
+        match self {
+            Self::Ref(validator) => validator.sanitise(value),
+            Self::AllOf(validator) => validator.sanitise(value),
+            Self::Properties(validator) => validator.sanitise(value),
+            Self::PatternProperties(validator) => validator.sanitise(value),
+            Self::AdditionalProperties(validator) => validator.sanitise(value),
+            // The other keywords do not restrict the allowed properties
+            Self::Type(_) | Self::Discriminator(_) | Self::ReadOnly(_) | Self::WriteOnly(_) | Self::Custom(_) => {}
+        }
+    }
+
+    pub(in crate) fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        match self {
+            Self::Ref(validator) => validator.collect_annotations(path, value, annotations),
+            Self::AllOf(validator) => validator.collect_annotations(path, value, annotations),
+            Self::Properties(validator) => validator.collect_annotations(path, value, annotations),
+            Self::PatternProperties(validator) => validator.collect_annotations(path, value, annotations),
+            Self::AdditionalProperties(validator) => validator.collect_annotations(path, value, annotations),
+            // The other keywords have no subschemas applied to the value
+            Self::Type(_) | Self::Discriminator(_) | Self::ReadOnly(_) | Self::WriteOnly(_) | Self::Custom(_) => self.is_valid(path, value),
         }
     }
 
@@ -31,32 +113,208 @@ impl DraftValidator {
         self.validation_errors(path, value).next().is_none()
     }
 
+    pub(in crate) fn initialise(&self, schema_cache: &HashMap<Url, Arc<Schema>>) {
+        match self {
+            Self::Ref(validator) => validator.resolve(schema_cache),
+            Self::Discriminator(validator) => validator.mapping.values().for_each(|mapped_schema| mapped_schema.resolve(schema_cache)),
+            // The other keywords reference their subschemas directly, as those are compiled before them
+            Self::AllOf(_) | Self::Properties(_) | Self::PatternProperties(_) | Self::AdditionalProperties(_) | Self::Type(_) | Self::ReadOnly(_) | Self::WriteOnly(_) | Self::Custom(_) => {}
+        }
+    }
+
     #[cfg(test)]
     pub(in crate) fn keyword_type(&self) -> KeywordType {
         match self {
             Self::Ref(validator) => validator.keyword_type(),
+            Self::AllOf(validator) => validator.keyword_type(),
             Self::Properties(validator) => validator.keyword_type(),
+            Self::PatternProperties(validator) => validator.keyword_type(),
+            Self::AdditionalProperties(validator) => validator.keyword_type(),
             Self::Type(validator) => validator.keyword_type(),
+            Self::Discriminator(validator) => validator.keyword_type(),
+            Self::ReadOnly(validator) => validator.keyword_type(),
+            Self::WriteOnly(validator) => validator.keyword_type(),
+            Self::Custom(validator) => validator.keyword_type(),
         }
     }
 }
 
-pub(in crate) fn compile_draft_validators<T: 'static + JsonType>(scope_builder: &mut ScopeBuilder<T>, schema: &Schema) -> Result<Vec<DraftValidator>, SchemaError> {
+// Keywords supported by each draft, in compilation order
+pub(in crate) fn draft_keywords(draft_version: DraftVersion) -> &'static [KeywordType] {
+    match draft_version {
+        DraftVersion::Draft4 => &[
+            KeywordType::Type,
+            KeywordType::Properties,
+            KeywordType::PatternProperties,
+            KeywordType::AdditionalProperties,
+            KeywordType::Ref,
+            KeywordType::AllOf,
+        ],
+        // `nullable` is handled by `type` and `example` is an annotation only, so neither is compiled on its own
+        DraftVersion::OpenApi30 => &[
+            KeywordType::Type,
+            KeywordType::Properties,
+            KeywordType::AdditionalProperties,
+            KeywordType::Ref,
+            KeywordType::AllOf,
+            KeywordType::Discriminator,
+            KeywordType::ReadOnly,
+            KeywordType::WriteOnly,
+        ],
+    }
+}
+
+// Keywords defined by each draft specification, including the ones that are not validated yet
+pub(in crate) fn draft_keyword_names(draft_version: DraftVersion) -> &'static [&'static str] {
+    match draft_version {
+        DraftVersion::Draft4 => &[
+            "$schema",
+            "id",
+            "$ref",
+            "title",
+            "description",
+            "default",
+            "definitions",
+            "multipleOf",
+            "maximum",
+            "exclusiveMaximum",
+            "minimum",
+            "exclusiveMinimum",
+            "maxLength",
+            "minLength",
+            "pattern",
+            "additionalItems",
+            "items",
+            "maxItems",
+            "minItems",
+            "uniqueItems",
+            "maxProperties",
+            "minProperties",
+            "required",
+            "additionalProperties",
+            "properties",
+            "patternProperties",
+            "dependencies",
+            "enum",
+            "type",
+            "allOf",
+            "anyOf",
+            "oneOf",
+            "not",
+            "format",
+        ],
+        DraftVersion::OpenApi30 => &[
+            "$ref",
+            "title",
+            "description",
+            "default",
+            "multipleOf",
+            "maximum",
+            "exclusiveMaximum",
+            "minimum",
+            "exclusiveMinimum",
+            "maxLength",
+            "minLength",
+            "pattern",
+            "items",
+            "maxItems",
+            "minItems",
+            "uniqueItems",
+            "maxProperties",
+            "minProperties",
+            "required",
+            "additionalProperties",
+            "properties",
+            "enum",
+            "type",
+            "allOf",
+            "anyOf",
+            "oneOf",
+            "not",
+            "format",
+            "nullable",
+            "discriminator",
+            "readOnly",
+            "writeOnly",
+            "xml",
+            "externalDocs",
+            "example",
+            "deprecated",
+        ],
+    }
+}
+
+// Keywords of draft_keyword_names that are meaningful without being validated (annotations and schema locators)
+pub(in crate) fn draft_annotation_keyword_names(draft_version: DraftVersion) -> &'static [&'static str] {
+    match draft_version {
+        DraftVersion::Draft4 => &["$schema", "id", "title", "description", "default", "definitions"],
+        // `nullable` is handled by `type`
+        DraftVersion::OpenApi30 => &["title", "description", "default", "nullable", "xml", "externalDocs", "example", "deprecated"],
+    }
+}
+
+// Name of the keyword compiled into keyword_type, if it is a draft keyword
+pub(in crate) fn draft_keyword_name(keyword_type: KeywordType) -> Option<&'static str> {
+    match keyword_type {
+        KeywordType::Type => Some("type"),
+        KeywordType::Properties => Some("properties"),
+        KeywordType::PatternProperties => Some("patternProperties"),
+        KeywordType::AdditionalProperties => Some("additionalProperties"),
+        KeywordType::Ref => Some("$ref"),
+        KeywordType::AllOf => Some("allOf"),
+        KeywordType::Discriminator => Some("discriminator"),
+        KeywordType::ReadOnly => Some("readOnly"),
+        KeywordType::WriteOnly => Some("writeOnly"),
+        KeywordType::Unknown | KeywordType::Vocabulary | KeywordType::Custom(_) => None,
+    }
+}
+
+fn compile_draft_validator<T: 'static + JsonType, J: JsonType>(
+    keyword_type: KeywordType,
+    scope_builder: &mut ScopeBuilder<T>,
+    path: &Url,
+    raw_schema: &J,
+) -> Result<Option<DraftValidator>, SchemaError> {
+    Ok(match keyword_type {
+        KeywordType::Type => type_::Type::compile(scope_builder, path, raw_schema)?.map(DraftValidator::Type),
+        KeywordType::Properties => properties_::Properties::compile(scope_builder, path, raw_schema)?.map(DraftValidator::Properties),
+        KeywordType::PatternProperties => pattern_properties_::PatternProperties::compile(scope_builder, path, raw_schema)?.map(DraftValidator::PatternProperties),
+        KeywordType::AdditionalProperties => additional_properties_::AdditionalProperties::compile(scope_builder, path, raw_schema)?.map(DraftValidator::AdditionalProperties),
+        KeywordType::Ref => ref_::Ref::compile(scope_builder, path, raw_schema)?.map(DraftValidator::Ref),
+        KeywordType::AllOf => all_of_::AllOf::compile(scope_builder, path, raw_schema)?.map(DraftValidator::AllOf),
+        KeywordType::Discriminator => discriminator_::Discriminator::compile(scope_builder, path, raw_schema)?.map(DraftValidator::Discriminator),
+        KeywordType::ReadOnly => read_write_only_::ReadOnly::compile(scope_builder, path, raw_schema)?.map(DraftValidator::ReadOnly),
+        KeywordType::WriteOnly => read_write_only_::WriteOnly::compile(scope_builder, path, raw_schema)?.map(DraftValidator::WriteOnly),
+        KeywordType::Unknown | KeywordType::Vocabulary | KeywordType::Custom(_) => None,
+    })
+}
+
+pub(in crate) fn compile_draft_validators<T: 'static + JsonType, J: JsonType>(
+    scope_builder: &mut ScopeBuilder<T>,
+    path: &Url,
+    raw_schema: &J,
+) -> Result<Vec<DraftValidator>, SchemaError> {
     let mut validators: Vec<DraftValidator> = Vec::new();
+    scope_builder.check_keywords(path, raw_schema)?;
 
-    match scope_builder.draft_version {
-        DraftVersion::Draft4 => {
-            if let Some(validator) = type_::Type::compile(scope_builder, schema)? {
-                validators.push(DraftValidator::Type(validator));
-            }
-            if let Some(validator) = properties_::Properties::compile(scope_builder, schema)? {
-                validators.push(DraftValidator::Properties(validator));
+    // All the supported drafts ignore the keywords next to `$ref`
+    let has_ref = raw_schema.get_attribute("$ref").is_some();
+    let keyword_types: &[KeywordType] = if has_ref { &[KeywordType::Ref] } else { draft_keywords(scope_builder.draft_version) };
+
+    for keyword_type in keyword_types {
+        if scope_builder.is_keyword_enabled(*keyword_type) {
+            if let Some(validator) = compile_draft_validator(*keyword_type, scope_builder, path, raw_schema)? {
+                validators.push(validator);
             }
-            if let Some(validator) = ref_::Ref::compile(scope_builder, schema)? {
-                validators.push(DraftValidator::Ref(validator));
+        }
+    }
+    if !has_ref {
+        for registration in scope_builder.custom_keywords() {
+            if let Some(validator) = custom_::Custom::compile(&registration, path, raw_schema)? {
+                validators.push(DraftValidator::Custom(validator));
             }
         }
-    };
+    }
     validators.shrink_to_fit();
     Ok(validators)
 }
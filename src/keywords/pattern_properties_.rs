@@ -0,0 +1,224 @@
+#[cfg(feature = "rayon")]
+use crate::iterator_utils::parallel::flat_map_validation_errors;
+use crate::{
+    iterator_utils::empty_iterator::EmptyIterator,
+    types::{
+        annotation::Annotation, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator,
+        validator_error_iterator::ValidationErrorIterator,
+    },
+};
+use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType, RustType};
+use regex::Regex;
+use std::{any::Any, collections::HashSet, fmt::Debug, sync::Arc};
+use url::Url;
+
+#[derive(Debug)]
+pub(in crate) struct PatternProperties {
+    pub(in crate) patterns: Vec<(Regex, Arc<Schema>)>,
+    // Properties declared by the sibling `properties` keyword, which are coerced by it
+    pub(in crate) properties: HashSet<String>,
+}
+
+fn into_str<'l, S: Into<&'l str>>(value: S) -> &'l str {
+    value.into()
+}
+
+// Patterns are not anchored, as defined by ECMA 262
+pub(in crate) fn compile_pattern(path: &Url, keyword_type: KeywordType, pattern: &str) -> Result<Regex, SchemaError> {
+    Regex::new(pattern).map_err(|error| SchemaError::Malformed {
+        path: path.clone(),
+        keyword: keyword_type,
+        detail: format!("Invalid pattern `{}`: {}", pattern, error),
+    })
+}
+
+fn pattern_path(schema_path: &Url, pattern: &str) -> Url {
+    let mut result = schema_path.clone();
+    result.set_fragment(Some(&format!(
+        "{}/patternProperties/{}",
+        schema_path.fragment().unwrap_or("").trim_end_matches('/'),
+        pattern.replace('~', "~0").replace('/', "~1"),
+    )));
+    result
+}
+
+impl Validator for PatternProperties {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        let pattern_properties_attribute = if let Some(value) = raw_schema.get_attribute("patternProperties") {
+            value
+        } else {
+            // patternProperties attribute is not there so we're done here
+            return Ok(None);
+        };
+        let pattern_properties_map = if let Some(value) = pattern_properties_attribute.as_object() {
+            value
+        } else {
+            return Err(SchemaError::Malformed {
+                path: path.clone(),
+                keyword: KeywordType::PatternProperties,
+                detail: format!(
+                    "Value has to be of `{}` type. {:?} has type `{}`.",
+                    into_str(PrimitiveType::Object),
+                    pattern_properties_attribute,
+                    into_str(pattern_properties_attribute.primitive_type())
+                ),
+            });
+        };
+
+        let mut patterns = Vec::new();
+        for (pattern, value) in pattern_properties_map.items() {
+            let regex = compile_pattern(path, KeywordType::PatternProperties, pattern)?;
+            if !value.is_object() {
+                return Err(SchemaError::Malformed {
+                    path: path.clone(),
+                    keyword: KeywordType::PatternProperties,
+                    detail: format!(
+                        "Values have to be of `{}` type. {:?} has type `{}`.",
+                        into_str(PrimitiveType::Object),
+                        value,
+                        into_str(value.primitive_type())
+                    ),
+                });
+            }
+            patterns.push((regex, scope_builder.schema(&pattern_path(path, pattern), value)?));
+        }
+
+        // Malformed properties are reported by their own validator
+        let properties = raw_schema
+            .get_attribute("properties")
+            .and_then(|value| value.as_object())
+            .map_or_else(HashSet::new, |properties| properties.items().map(|(property_name, _)| property_name.to_string()).collect());
+
+        Ok(Some(Self { patterns, properties }))
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::PatternProperties
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if let Some(object) = value.as_object() {
+            ValidationErrorIterator::new(
+                object
+                    .items()
+                    .flat_map(|(property_name, property_value)| {
+                        self.patterns
+                            .iter()
+                            .filter(move |(regex, _)| regex.is_match(property_name))
+                            .flat_map(move |(_, schema)| schema.validation_errors(&format!("{}/{}", path, property_name), property_value))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            ValidationErrorIterator::new(EmptyIterator::new())
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if let Some(object) = value.as_object() {
+            flat_map_validation_errors(object.items().collect(), |(property_name, property_value)| {
+                self.patterns
+                    .iter()
+                    .filter(|(regex, _)| regex.is_match(property_name))
+                    .flat_map(|(_, schema)| schema.par_validation_errors(&format!("{}/{}", path, property_name), property_value))
+                    .collect()
+            })
+        } else {
+            ValidationErrorIterator::new(EmptyIterator::new())
+        }
+    }
+
+    // Properties are coerced only if a single schema applies to them, otherwise the conversion could be ambiguous
+    fn coerce(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            for (property_name, property_value) in object.iter_mut().filter(|(property_name, _)| !self.properties.contains(*property_name)) {
+                let mut matching_schemas = self.patterns.iter().filter(|(regex, _)| regex.is_match(property_name));
+                if let (Some((_, schema)), None) = (matching_schemas.next(), matching_schemas.next()) {
+                    schema.coerce(property_value);
+                }
+            }
+        }
+    }
+
+    fn sanitise(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            for (property_name, property_value) in object.iter_mut() {
+                for (_, schema) in self.patterns.iter().filter(|(regex, _)| regex.is_match(property_name)) {
+                    schema.sanitise(property_value);
+                }
+            }
+        }
+    }
+
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        value.as_object().map_or(true, |object| {
+            object.items().all(|(property_name, property_value)| {
+                self.patterns
+                    .iter()
+                    .filter(|(regex, _)| regex.is_match(property_name))
+                    .all(|(_, schema)| schema.collect_annotations(&format!("{}/{}", path, property_name), property_value, annotations))
+            })
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use crate::types::{
+        keyword_type::KeywordType,
+        schema::{draft4_schema, initialised_draft4_schema, Schema},
+        schema_error::SchemaError,
+        validation_error::ValidationError,
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+
+    // Testing constants
+    fn pattern_properties_schema() -> Schema {
+        draft4_schema(rust_type!({"patternProperties": {"^x-": {"type": "string"}, "id$": {"type": "integer"}}})).expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"patternProperties": []}), "Value has to be of `object` type. List([]) has type `array`.")]
+    #[test_case(rust_type!({"patternProperties": {"^a": 1}}), "Values have to be of `object` type. Integer(1) has type `integer`.")]
+    #[test_case(rust_type!({"patternProperties": {"(": {}}}), "Invalid pattern `(`: ")]
+    fn build_pattern_properties_object_invalid(raw_schema: RustType, expected_malformed_error_detail: &str) {
+        assert!(matches!(
+            draft4_schema(raw_schema),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::PatternProperties,
+                detail,
+                ..
+            }) if detail.starts_with(expected_malformed_error_detail)
+        ));
+    }
+
+    #[test_case(&rust_type!("text"), &[])]
+    #[test_case(&rust_type!({"x-name": "name", "user-id": 1, "other": null}), &[])]
+    #[test_case(&rust_type!({"x-name": 1}), &[ValidationError::new("#/x-name", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&rust_type!({"x-id": 1}), &[ValidationError::new("#/x-id", KeywordType::Type, "Invalid Type")])]
+    fn validate(object: &RustType, expected_validation_errors: &[ValidationError]) {
+        pattern_properties_schema().do_on_validator(KeywordType::PatternProperties, &|maybe_draft_validator| {
+            let validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+        });
+    }
+
+    #[test_case(&rust_type!({"x-count": "1", "x-id": "2", "id": "3", "other": "4"}) => rust_type!({"x-count": 1, "x-id": "2", "id": "3", "other": "4"}))]
+    fn coerce(value: &RustType) -> RustType {
+        let schema = initialised_draft4_schema(rust_type!({
+            "properties": {"id": {"type": "string"}},
+            "patternProperties": {"^x-": {"type": "integer"}, "id$": {"type": "boolean"}, "^id$": {"type": "integer"}},
+        }))
+        .expect("Schema is supposed to be valid");
+        schema.validate_with_coercion("#", value).0
+    }
+}
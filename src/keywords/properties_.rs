@@ -1,10 +1,13 @@
+#[cfg(feature = "rayon")]
+use crate::iterator_utils::parallel::flat_map_validation_errors;
 use crate::{
     iterator_utils::empty_iterator::EmptyIterator,
     types::{
-        keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator, validator_error_iterator::ValidationErrorIterator,
+        annotation::Annotation, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator,
+        validator_error_iterator::ValidationErrorIterator,
     },
 };
-use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType};
+use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType, RustType};
 use std::{any::Any, collections::HashMap, fmt::Debug, sync::Arc};
 use url::Url;
 
@@ -13,11 +16,6 @@ pub(in crate) struct Properties {
     pub(in crate) properties: HashMap<String, Arc<Schema>>,
 }
 
-#[allow(unsafe_code)]
-unsafe impl Sync for Properties {}
-#[allow(unsafe_code)]
-unsafe impl Send for Properties {}
-
 fn into_str<'l, S: Into<&'l str>>(value: S) -> &'l str {
     value.into()
 }
@@ -40,17 +38,17 @@ fn property_path(schema_path: &Url, property_name: &str) -> Url {
     result.set_fragment(Some(&format!(
         "{}/properties/{}",
         schema_path.fragment().unwrap_or("").trim_end_matches('/'),
-        property_name,
+        property_name.replace('~', "~0").replace('/', "~1"),
     )));
     result
 }
 
 impl Validator for Properties {
-    fn compile<T: 'static + JsonType>(scope_builder: &mut ScopeBuilder<T>, schema: &Schema) -> Result<Option<Self>, SchemaError>
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
     where
         Self: Sized,
     {
-        let properties_attribute = if let Some(value) = schema.get_attribute("properties") {
+        let properties_attribute = if let Some(value) = raw_schema.get_attribute("properties") {
             value
         } else {
             // properties attribute is not there so we're done here
@@ -59,50 +57,27 @@ impl Validator for Properties {
         let properties_map = if let Some(value) = properties_attribute.as_object() {
             value
         } else {
-            return Err(malformed_properties(&schema.path, properties_attribute));
+            return Err(malformed_properties(path, properties_attribute));
         };
 
-        let (properties, errors) = properties_map
-            .items()
-            .map(|(key, value)| {
-                let path = property_path(&schema.path, key);
-                if value.is_object() {
-                    match scope_builder.schema(&path, value) {
-                        Ok(arc_schema) => Ok((key.to_string(), arc_schema)),
-                        Err(schema_error) => {
-                            unreachable!(
-                                "Unexpected error in the schema: path: {:?}, raw_schema: {:?}, schema_error: {:?}",
-                                path, value, schema_error,
-                            );
-                        }
-                    }
-                } else {
-                    Err(path)
-                }
-            })
-            .partition::<Vec<_>, _>(Result::is_ok);
-
-        if errors.is_empty() {
-            Ok(Some(Self {
-                properties: properties
-                    .into_iter()
-                    .map(|result| match result {
-                        Ok((path, arc_schema)) => (path, arc_schema),
-                        Err(path) => unreachable!("No errors are expected in the properties. path: {:?}", path),
-                    })
-                    .collect::<HashMap<_, _>>(),
-            }))
+        let mut properties = HashMap::new();
+        let mut faulty_urls = Vec::new();
+        for (key, value) in properties_map.items() {
+            let property_url = property_path(path, key);
+            if value.is_object() {
+                // Errors of the property schemas are propagated as they are, so they keep pointing to the offending keyword
+                let _ = properties.insert(key.to_string(), scope_builder.schema(&property_url, value)?);
+            } else {
+                faulty_urls.push(property_url.to_string());
+            }
+        }
+
+        if faulty_urls.is_empty() {
+            Ok(Some(Self { properties }))
         } else {
-            let faulty_urls = errors
-                .iter()
-                .map(|result| match result {
-                    Ok(arc_schema) => unreachable!("No successes are expected in the errors. schema: {:?}", arc_schema),
-                    Err(path) => path.as_str(),
-                })
-                .collect::<Vec<_>>()
-                .join(", ");
+            let faulty_urls = faulty_urls.join(", ");
             Err(SchemaError::Malformed {
-                path: schema.path.clone(),
+                path: path.clone(),
                 keyword: KeywordType::Properties,
                 detail: format!(
                     "Values of properties object must be of object type. The following URLs are recognized as faulty: {}",
@@ -135,9 +110,72 @@ impl Validator for Properties {
     }
 
     fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
-        self.properties
-            .iter()
-            .all(|(attribute_name, schema)| schema.is_valid(&format!("{}/{}", path, attribute_name), value))
+        value.as_object().map_or(true, |object| {
+            self.properties.iter().all(|(attribute_name, schema)| {
+                object
+                    .get_attribute(attribute_name)
+                    .map_or(true, |attribute_value| schema.is_valid(&format!("{}/{}", path, attribute_name), attribute_value))
+            })
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if let Some(object) = value.as_object() {
+            let properties = self
+                .properties
+                .iter()
+                .filter_map(|(attribute_name, schema)| object.get_attribute(attribute_name).map(|attribute_value| (attribute_name, schema, attribute_value)))
+                .collect::<Vec<_>>();
+            flat_map_validation_errors(properties, |(attribute_name, schema, attribute_value)| {
+                schema.par_validation_errors(&format!("{}/{}", path, attribute_name), attribute_value).collect()
+            })
+        } else {
+            ValidationErrorIterator::new(EmptyIterator::new())
+        }
+    }
+
+    fn apply_defaults(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            for (property_name, schema) in &self.properties {
+                if let Some(property_value) = object.get_mut(property_name) {
+                    schema.apply_defaults(property_value);
+                } else if let Some(mut property_value) = schema.default_value() {
+                    schema.apply_defaults(&mut property_value);
+                    let _ = object.insert(property_name.clone(), property_value);
+                }
+            }
+        }
+    }
+
+    fn coerce(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            for (property_name, schema) in &self.properties {
+                if let Some(property_value) = object.get_mut(property_name) {
+                    schema.coerce(property_value);
+                }
+            }
+        }
+    }
+
+    fn sanitise(&self, value: &mut RustType) {
+        if let RustType::Object(object) = value {
+            for (property_name, schema) in &self.properties {
+                if let Some(property_value) = object.get_mut(property_name) {
+                    schema.sanitise(property_value);
+                }
+            }
+        }
+    }
+
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        value.as_object().map_or(true, |object| {
+            self.properties.iter().all(|(property_name, schema)| {
+                object.get_attribute(property_name).map_or(true, |property_value| {
+                    schema.collect_annotations(&format!("{}/{}", path, property_name), property_value, annotations)
+                })
+            })
+        })
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -154,6 +192,7 @@ mod tests {
     #[test_case("memory://", "prop", "memory://#/properties/prop")]
     #[test_case("memory:///path", "prop", "memory:///path#/properties/prop")]
     #[test_case("memory:///path#/fragment", "prop", "memory:///path#/fragment/properties/prop")]
+    #[test_case("memory://", "a/b~c", "memory://#/properties/a~1b~0c")]
     fn test_property_path(schema_url: &str, property_name: &str, expected_url: &str) {
         assert_eq!(property_path(&Url::parse(schema_url).unwrap(), property_name).as_str(), expected_url);
     }
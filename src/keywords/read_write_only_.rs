@@ -0,0 +1,140 @@
+use crate::types::{
+    keyword_type::KeywordType, openapi_context::OpenApiContext, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError, validator::Validator,
+    validator_error_iterator::ValidationErrorIterator,
+};
+use json_trait_rs::JsonType;
+use std::{any::Any, fmt::Debug};
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(in crate) struct ReadOnly;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(in crate) struct WriteOnly;
+
+fn into_str<'l, S: Into<&'l str>>(value: S) -> &'l str {
+    value.into()
+}
+
+// Returns true if the keyword is set to true and the instance is validated in the context that forbids it
+fn is_forbidden<T: JsonType, J: JsonType>(
+    scope_builder: &ScopeBuilder<T>,
+    path: &Url,
+    raw_schema: &J,
+    keyword_type: KeywordType,
+    attribute_name: &str,
+    forbidden_context: OpenApiContext,
+) -> Result<bool, SchemaError> {
+    let attribute = if let Some(value) = raw_schema.get_attribute(attribute_name) {
+        value
+    } else {
+        // attribute is not there so we're done here
+        return Ok(false);
+    };
+
+    if let Some(value) = attribute.as_boolean() {
+        Ok(value && scope_builder.openapi_context == Some(forbidden_context))
+    } else {
+        Err(SchemaError::Malformed {
+            path: path.clone(),
+            keyword: keyword_type,
+            detail: format!("Value has to be of `boolean` type. {:?} has type `{}`.", attribute, into_str(attribute.primitive_type())),
+        })
+    }
+}
+
+impl Validator for ReadOnly {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        if is_forbidden(scope_builder, path, raw_schema, KeywordType::ReadOnly, "readOnly", OpenApiContext::Request)? {
+            Ok(Some(Self))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::ReadOnly
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, _value: &T) -> ValidationErrorIterator {
+        ValidationErrorIterator::from(ValidationError::new(path, KeywordType::ReadOnly, "Read-only value is not allowed in requests"))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Validator for WriteOnly {
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized,
+    {
+        if is_forbidden(scope_builder, path, raw_schema, KeywordType::WriteOnly, "writeOnly", OpenApiContext::Response)? {
+            Ok(Some(Self))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn keyword_type(&self) -> KeywordType {
+        KeywordType::WriteOnly
+    }
+
+    fn validation_errors<T: 'static + JsonType>(&self, path: &str, _value: &T) -> ValidationErrorIterator {
+        ValidationErrorIterator::from(ValidationError::new(path, KeywordType::WriteOnly, "Write-only value is not allowed in responses"))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod validator_tests {
+    use crate::types::{
+        keyword_type::KeywordType,
+        openapi_context::OpenApiContext,
+        schema::{initialised_openapi30_schema, openapi30_schema, Schema},
+        schema_error::SchemaError,
+        validation_error::ValidationError,
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use std::sync::Arc;
+    use test_case::test_case;
+
+    // Testing constants
+    fn access_mode_schema(openapi_context: Option<OpenApiContext>) -> Arc<Schema> {
+        initialised_openapi30_schema(
+            rust_type!({"properties": {"id": {"type": "integer", "readOnly": true}, "password": {"type": "string", "writeOnly": true}}}),
+            openapi_context,
+        )
+        .expect("Schema is supposed to be valid")
+    }
+
+    // Tests
+    #[test_case(rust_type!({"readOnly": 1}), KeywordType::ReadOnly)]
+    #[test_case(rust_type!({"writeOnly": "true"}), KeywordType::WriteOnly)]
+    fn build_access_mode_invalid(raw_schema: RustType, expected_keyword_type: KeywordType) {
+        assert!(matches!(
+            openapi30_schema(raw_schema, None),
+            Err(SchemaError::Malformed { keyword, .. }) if keyword == expected_keyword_type
+        ));
+    }
+
+    #[test_case(&access_mode_schema(None), &rust_type!({"id": 1, "password": "secret"}), &[])]
+    #[test_case(&access_mode_schema(Some(OpenApiContext::Request)), &rust_type!({"password": "secret"}), &[])]
+    #[test_case(&access_mode_schema(Some(OpenApiContext::Request)), &rust_type!({"id": 1, "password": "secret"}), &[
+        ValidationError::new("#/id", KeywordType::ReadOnly, "Read-only value is not allowed in requests"),
+    ])]
+    #[test_case(&access_mode_schema(Some(OpenApiContext::Response)), &rust_type!({"id": 1}), &[])]
+    #[test_case(&access_mode_schema(Some(OpenApiContext::Response)), &rust_type!({"id": 1, "password": "secret"}), &[
+        ValidationError::new("#/password", KeywordType::WriteOnly, "Write-only value is not allowed in responses"),
+    ])]
+    fn validate(schema: &Schema, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+}
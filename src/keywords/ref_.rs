@@ -1,31 +1,77 @@
 use crate::types::{
-    keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator::Validator, validator_error_iterator::ValidationErrorIterator,
+    annotation::Annotation,
+    keyword_type::KeywordType,
+    schema::Schema,
+    schema_error::SchemaError,
+    scope_builder::{schema_cache_key, ScopeBuilder},
+    validation_error::ValidationError,
+    validator::Validator,
+    validator_error_iterator::ValidationErrorIterator,
+};
+use json_trait_rs::{JsonType, RustType};
+use once_cell::sync::OnceCell;
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Debug,
+    marker::PhantomData,
+    sync::{Arc, Weak},
 };
-use json_trait_rs::JsonType;
-use std::{any::Any, fmt::Debug, marker::PhantomData, sync::Arc};
 use url::Url;
 
+#[derive(Debug, Clone)]
+enum ReferencedSchema {
+    Compiled(Arc<Schema>),
+    // Schemas still being compiled contain the reference (recursive references), so they are retained by the
+    // enclosing schema (and by the ScopeBuilder/Scope cache). Retaining them via Arc would never release them.
+    Recursive(Weak<Schema>),
+}
+
 #[derive(Debug, Clone)]
 pub(in crate) struct Ref {
     pub(in crate) referenced_uri: Url,
-    pub(in crate) referenced_schema: Arc<Schema>,
+    // References to schemas still being compiled (recursive references) are resolved by ScopeBuilder::build
+    referenced_schema: OnceCell<ReferencedSchema>,
 }
 
-#[allow(unsafe_code)]
-unsafe impl Sync for Ref {}
-#[allow(unsafe_code)]
-unsafe impl Send for Ref {}
+impl Ref {
+    pub(in crate) fn create<T: 'static + JsonType>(scope_builder: &mut ScopeBuilder<T>, referenced_uri: Url) -> Result<Self, SchemaError> {
+        let referenced_schema = OnceCell::new();
+        if !scope_builder.is_compiling(&referenced_uri) {
+            let _ = referenced_schema.set(ReferencedSchema::Compiled(scope_builder.referenced_schema(&referenced_uri)?));
+        }
+        Ok(Self {
+            referenced_uri,
+            referenced_schema,
+        })
+    }
+
+    // None if the referenced schema was still being compiled and ScopeBuilder::build was not called yet,
+    // or if the schemas containing a recursive reference were released
+    pub(in crate) fn referenced_schema(&self) -> Option<Arc<Schema>> {
+        match self.referenced_schema.get()? {
+            ReferencedSchema::Compiled(referenced_schema) => Some(referenced_schema.clone()),
+            ReferencedSchema::Recursive(referenced_schema) => referenced_schema.upgrade(),
+        }
+    }
+
+    pub(in crate) fn resolve(&self, schema_cache: &HashMap<Url, Arc<Schema>>) {
+        if let Some(referenced_schema) = schema_cache.get(&schema_cache_key(&self.referenced_uri)) {
+            let _ = self.referenced_schema.set(ReferencedSchema::Recursive(Arc::downgrade(referenced_schema)));
+        }
+    }
+}
 
-fn full_uri(base_path: &Url, json_reference: &str) -> Url {
+pub(in crate) fn full_uri(base_path: &Url, json_reference: &str) -> Url {
     base_path.join(json_reference).unwrap()
 }
 
 impl Validator for Ref {
-    fn compile<T: 'static + JsonType>(scope_builder: &mut ScopeBuilder<T>, schema: &Schema) -> Result<Option<Self>, SchemaError>
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
     where
         Self: Sized,
     {
-        let ref_attribute = if let Some(value) = schema.get_attribute("$ref") {
+        let ref_attribute = if let Some(value) = raw_schema.get_attribute("$ref") {
             value
         } else {
             // $ref attribute is not there so we're done here
@@ -39,13 +85,7 @@ impl Validator for Ref {
             return Ok(None);
         };
 
-        let referenced_uri = full_uri(&schema.path, ref_value);
-        let referenced_raw_schema: Arc<T> = scope_builder.retrieve_schema(&referenced_uri)?;
-        let referenced_schema = scope_builder.schema(&referenced_uri, &referenced_raw_schema.to_rust_type())?;
-        Ok(Some(Self {
-            referenced_uri,
-            referenced_schema,
-        }))
+        Self::create(scope_builder, full_uri(path, ref_value)).map(Some)
     }
 
     fn keyword_type(&self) -> KeywordType {
@@ -53,11 +93,47 @@ impl Validator for Ref {
     }
 
     fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
-        self.referenced_schema.validation_errors(path, value)
+        match self.referenced_schema() {
+            Some(referenced_schema) => referenced_schema.validation_errors(path, value),
+            None => ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Ref, &format!("Unresolved reference {}", self.referenced_uri))),
+        }
     }
 
     fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
-        self.referenced_schema.is_valid(path, value)
+        self.referenced_schema().map_or(false, |referenced_schema| referenced_schema.is_valid(path, value))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        match self.referenced_schema() {
+            Some(referenced_schema) => referenced_schema.par_validation_errors(path, value),
+            None => self.validation_errors(path, value),
+        }
+    }
+
+    fn apply_defaults(&self, value: &mut RustType) {
+        if let Some(referenced_schema) = self.referenced_schema() {
+            referenced_schema.apply_defaults(value);
+        }
+    }
+
+    fn coerce(&self, value: &mut RustType) {
+        if let Some(referenced_schema) = self.referenced_schema() {
+            referenced_schema.coerce(value);
+        }
+    }
+
+    fn sanitise(&self, value: &mut RustType) {
+        if let Some(referenced_schema) = self.referenced_schema() {
+            referenced_schema.sanitise(value);
+        }
+    }
+
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        match self.referenced_schema() {
+            Some(referenced_schema) => referenced_schema.collect_annotations(path, value, annotations),
+            None => self.is_valid(path, value),
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -104,7 +180,7 @@ mod validator_tests {
         types::{
             draft_version::DraftVersion,
             keyword_type::KeywordType,
-            schema::{draft4_schema, Schema},
+            schema::{draft4_schema, initialised_draft4_schema, Schema},
             schema_error::SchemaError,
             validation_error::ValidationError,
             validator::Validator,
@@ -234,4 +310,38 @@ mod validator_tests {
             assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
         });
     }
+
+    #[test_case(
+        rust_type!({"properties": {"foo": {"$ref": "#"}}, "additionalProperties": false}),
+        &rust_type!({"foo": {"foo": false}}),
+        &[]
+    )]
+    #[test_case(
+        rust_type!({"properties": {"foo": {"$ref": "#"}}, "additionalProperties": false}),
+        &rust_type!({"foo": {"foo": {"bar": 1}}}),
+        &[ValidationError::new("#/foo/foo/bar", KeywordType::AdditionalProperties, "Additional property is not allowed")]
+    )]
+    #[test_case(
+        rust_type!({
+            "definitions": {
+                "a": {"properties": {"b": {"$ref": "#/definitions/b"}}},
+                "b": {"properties": {"a": {"$ref": "#/definitions/a"}, "value": {"type": "string"}}},
+            },
+            "$ref": "#/definitions/a",
+        }),
+        &rust_type!({"b": {"a": {"b": {"value": 1}}}}),
+        &[ValidationError::new("#/b/a/b/value", KeywordType::Type, "Invalid Type")]
+    )]
+    fn validate_recursive_references(raw_schema: RustType, object: &RustType, expected_validation_errors: &[ValidationError]) {
+        let schema = initialised_draft4_schema(raw_schema).expect("Schema is supposed to be valid");
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+
+    #[test]
+    fn release_recursive_schemas() {
+        let schema = initialised_draft4_schema(rust_type!({"properties": {"child": {"$ref": "#"}}})).expect("Schema is supposed to be valid");
+        let released_schema = Arc::downgrade(&schema);
+        drop(schema);
+        assert!(released_schema.upgrade().is_none());
+    }
 }
@@ -1,11 +1,11 @@
 use crate::{
     iterator_utils::empty_iterator::EmptyIterator,
     types::{
-        keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError, validator::Validator,
+        draft_version::DraftVersion, keyword_type::KeywordType, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError, validator::Validator,
         validator_error_iterator::ValidationErrorIterator,
     },
 };
-use json_trait_rs::{Error, JsonType, PrimitiveType};
+use json_trait_rs::{Error, JsonType, PrimitiveType, RustType};
 use std::{any::Any, collections::HashSet, convert::TryFrom, fmt::Debug};
 use url::Url;
 
@@ -14,11 +14,6 @@ pub(in crate) struct Type {
     pub(in crate) types: HashSet<PrimitiveType>,
 }
 
-#[allow(unsafe_code)]
-unsafe impl Sync for Type {}
-#[allow(unsafe_code)]
-unsafe impl Send for Type {}
-
 fn malformed_error(path: &Url, error: &Error) -> SchemaError {
     SchemaError::Malformed {
         path: path.clone(),
@@ -44,12 +39,93 @@ fn malformed_type<J: JsonType>(path: &Url, value: &J) -> SchemaError {
     }
 }
 
+impl Type {
+    fn matches<T: JsonType>(&self, value: &T) -> bool {
+        match value.primitive_type() {
+            // Integers are a subset of numbers, while numbers (even with zero fractional part, ie. `1.0`) are not integers in Draft 4
+            PrimitiveType::Integer => self.types.contains(&PrimitiveType::Integer) || self.types.contains(&PrimitiveType::Number),
+            value_primitive_type => self.types.contains(&value_primitive_type),
+        }
+    }
+}
+
+fn is_digits(string_value: &str) -> bool {
+    !string_value.is_empty() && string_value.chars().all(|character| character.is_ascii_digit())
+}
+
+fn without_sign<'s>(string_value: &'s str, signs: &[char]) -> &'s str {
+    if string_value.starts_with(signs) {
+        &string_value[1..]
+    } else {
+        string_value
+    }
+}
+
+// Strings with the JSON representation of an integer, so the conversion round trips (ie. `+5` and `007` are not integers)
+fn is_json_integer(string_value: &str) -> bool {
+    let digits = without_sign(string_value, &['-']);
+    digits == "0" || (is_digits(digits) && !digits.starts_with('0'))
+}
+
+// Strings with the JSON representation of a number (ie. `.5`, `5.` and `+5.0` are not numbers)
+fn is_json_number(string_value: &str) -> bool {
+    let (mantissa, exponent) = match string_value.find(&['e', 'E'][..]) {
+        Some(index) => (&string_value[..index], Some(&string_value[index + 1..])),
+        None => (string_value, None),
+    };
+    let (integer_part, fraction_digits) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], Some(&mantissa[index + 1..])),
+        None => (mantissa, None),
+    };
+    is_json_integer(integer_part) && fraction_digits.map_or(true, is_digits) && exponent.map_or(true, |exponent| is_digits(without_sign(exponent, &['+', '-'])))
+}
+
+// Lossless conversion of a scalar into one of the given types, the first successful conversion wins
+fn coerce_scalar(types: &HashSet<PrimitiveType>, value: &RustType) -> Option<RustType> {
+    let string_value = if let RustType::String(string_value) = value {
+        Some(string_value.as_str())
+    } else {
+        None
+    };
+    [
+        PrimitiveType::Integer,
+        PrimitiveType::Number,
+        PrimitiveType::Boolean,
+        PrimitiveType::Null,
+        PrimitiveType::Array,
+    ]
+    .iter()
+    .filter(|primitive_type| types.contains(*primitive_type))
+    .find_map(|primitive_type| match primitive_type {
+        PrimitiveType::Integer => string_value
+            .filter(|string_value| is_json_integer(string_value))
+            .and_then(|string_value| string_value.parse::<i128>().ok())
+            .map(RustType::Integer),
+        // Integral strings are kept as integers, as they are valid numbers as well
+        PrimitiveType::Number => string_value.filter(|string_value| is_json_number(string_value)).and_then(|string_value| {
+            if is_json_integer(string_value) {
+                string_value.parse::<i128>().ok().map(RustType::Integer)
+            } else {
+                string_value.parse::<f64>().ok().filter(|number| number.is_finite()).map(RustType::Number)
+            }
+        }),
+        PrimitiveType::Boolean => match string_value {
+            Some("true") => Some(RustType::Boolean(true)),
+            Some("false") => Some(RustType::Boolean(false)),
+            _ => None,
+        },
+        PrimitiveType::Null if string_value == Some("null") => Some(RustType::Null),
+        PrimitiveType::Array if !matches!(value.primitive_type(), PrimitiveType::Array | PrimitiveType::Object) => Some(RustType::List(vec![value.clone()])),
+        _ => None,
+    })
+}
+
 impl Validator for Type {
-    fn compile<T: 'static + JsonType>(_scope_builder: &mut ScopeBuilder<T>, schema: &Schema) -> Result<Option<Self>, SchemaError>
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
     where
         Self: Sized,
     {
-        let type_attribute = if let Some(value) = schema.get_attribute("type") {
+        let type_attribute = if let Some(value) = raw_schema.get_attribute("type") {
             value
         } else {
             // type attribute is not there so we're done here
@@ -61,34 +137,40 @@ impl Validator for Type {
             let primitive_type = match PrimitiveType::try_from(type_str) {
                 Ok(value) => value,
                 Err(ref error) => {
-                    return Err(malformed_error(&schema.path, error));
+                    return Err(malformed_error(path, error));
                 }
             };
             let _ = types.insert(primitive_type);
         } else if let Some(type_array) = type_attribute.as_array() {
-            if type_array.is_empty() {
-                return Err(SchemaError::Malformed {
-                    path: schema.path.clone(),
-                    keyword: KeywordType::Type,
-                    detail: "This array MUST have at least one element.".to_string(),
-                });
-            }
-
             for array_item in type_array {
                 if let Some(type_str) = array_item.as_string() {
                     let primitive_type = match PrimitiveType::try_from(type_str) {
                         Ok(value) => value,
                         Err(ref error) => {
-                            return Err(malformed_error(&schema.path, error));
+                            return Err(malformed_error(path, error));
                         }
                     };
                     let _ = types.insert(primitive_type);
                 } else {
-                    return Err(malformed_type(&schema.path, array_item));
+                    return Err(malformed_type(path, array_item));
                 }
             }
+
+            // Every item of a non empty array is either inserted or reported as error, so no types means an empty array
+            if types.is_empty() {
+                return Err(SchemaError::Malformed {
+                    path: path.clone(),
+                    keyword: KeywordType::Type,
+                    detail: "This array MUST have at least one element.".to_string(),
+                });
+            }
         } else {
-            return Err(malformed_type(&schema.path, type_attribute));
+            return Err(malformed_type(path, type_attribute));
+        }
+
+        // OpenAPI 3.0 has no `null` type, null values are allowed via the `nullable` keyword
+        if scope_builder.draft_version == DraftVersion::OpenApi30 && raw_schema.get_attribute("nullable").and_then(|value| value.as_boolean()) == Some(true) {
+            let _ = types.insert(PrimitiveType::Null);
         }
 
         Ok(Some(Self { types }))
@@ -99,12 +181,18 @@ impl Validator for Type {
     }
 
     fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
-        let value_primitive_type = value.primitive_type();
-
-        if self.types.iter().all(|primitive_type| primitive_type != &value_primitive_type) {
-            ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Type, "Invalid Type"))
-        } else {
+        if self.matches(value) {
             ValidationErrorIterator::new(EmptyIterator::new())
+        } else {
+            ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Type, "Invalid Type"))
+        }
+    }
+
+    fn coerce(&self, value: &mut RustType) {
+        if !self.matches(value) {
+            if let Some(coerced_value) = coerce_scalar(&self.types, value) {
+                *value = coerced_value;
+            }
         }
     }
 
@@ -121,7 +209,7 @@ mod validator_tests {
         keywords::DraftValidator,
         types::{
             keyword_type::KeywordType,
-            schema::{draft4_schema, Schema},
+            schema::{draft4_schema, initialised_draft4_schema, openapi30_schema, Schema},
             schema_error::SchemaError,
             validation_error::ValidationError,
         },
@@ -139,6 +227,18 @@ mod validator_tests {
         draft4_schema(rust_type!({"type": ["boolean", "string"]})).expect("Schema is supposed to be valid")
     }
 
+    fn integer_schema() -> Schema {
+        draft4_schema(rust_type!({"type": "integer"})).expect("Schema is supposed to be valid")
+    }
+
+    fn number_schema() -> Schema {
+        draft4_schema(rust_type!({"type": "number"})).expect("Schema is supposed to be valid")
+    }
+
+    fn nullable_string_schema() -> Schema {
+        openapi30_schema(rust_type!({"type": "string", "nullable": true}), None).expect("Schema is supposed to be valid")
+    }
+
     // Tests
     #[test_case(rust_type!({"type": "string"}), &hash_set![PrimitiveType::String])]
     #[test_case(rust_type!({"type": "integer"}), &hash_set![PrimitiveType::Integer])]
@@ -155,6 +255,26 @@ mod validator_tests {
         });
     }
 
+    #[test_case(rust_type!({"type": "string", "nullable": true}), &hash_set![PrimitiveType::String, PrimitiveType::Null])]
+    #[test_case(rust_type!({"type": "string", "nullable": false}), &hash_set![PrimitiveType::String])]
+    fn build_openapi30_type_object_valid(raw_schema: RustType, expected_types: &HashSet<PrimitiveType>) {
+        let schema = openapi30_schema(raw_schema, None).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::Type, &|maybe_draft_validator| {
+            let draft_validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
+            assert!(matches!(
+                draft_validator,
+                DraftValidator::Type(Type { types, ..})
+                if types == expected_types
+            ))
+        });
+    }
+
+    #[test]
+    fn draft4_ignores_nullable() {
+        let schema = initialised_draft4_schema(rust_type!({"type": "string", "nullable": true})).expect("Schema is supposed to be valid");
+        assert!(!schema.is_valid("#", &rust_type!(null)));
+    }
+
     #[test_case(
         rust_type!({"type": "not-real-type"}),
         "Unsupported primitive type `not-real-type`. Available types are defined by `json_trait_rs::PrimitiveType::VARIANTS`"
@@ -192,10 +312,49 @@ mod validator_tests {
     #[test_case(&string_or_bool_schema(), &rust_type!("text"), &[])]
     #[test_case(&string_or_bool_schema(), &rust_type!(true), &[])]
     #[test_case(&string_or_bool_schema(), &rust_type!(1), &[ValidationError::new("#/", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&integer_schema(), &rust_type!(1), &[])]
+    #[test_case(&integer_schema(), &rust_type!(1.0), &[ValidationError::new("#/", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&integer_schema(), &rust_type!(1.5), &[ValidationError::new("#/", KeywordType::Type, "Invalid Type")])]
+    #[test_case(&number_schema(), &rust_type!(1), &[])]
+    #[test_case(&number_schema(), &rust_type!(1.5), &[])]
+    #[test_case(&nullable_string_schema(), &rust_type!(null), &[])]
+    #[test_case(&nullable_string_schema(), &rust_type!("text"), &[])]
+    #[test_case(&nullable_string_schema(), &rust_type!(1), &[ValidationError::new("#/", KeywordType::Type, "Invalid Type")])]
     fn validate(schema: &Schema, object: &RustType, expected_validation_errors: &[ValidationError]) {
         schema.do_on_validator(KeywordType::Type, &|maybe_draft_validator| {
             let validator = maybe_draft_validator.expect("Expected validator to be found and of correct type");
             assert_eq!(validator.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
         });
     }
+
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("42") => rust_type!(42))]
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("-42") => rust_type!(-42))]
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("0") => rust_type!(0))]
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("+5") => rust_type!("+5"))]
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("007") => rust_type!("007"))]
+    #[test_case(rust_type!({"type": "integer"}), rust_type!("4.2") => rust_type!("4.2"))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("4.2") => rust_type!(4.2))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("42") => rust_type!(42))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("-0.5e-3") => rust_type!(-0.0005))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("NaN") => rust_type!("NaN"))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("+4.2") => rust_type!("+4.2"))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!("04.2") => rust_type!("04.2"))]
+    #[test_case(rust_type!({"type": "number"}), rust_type!(".5") => rust_type!(".5"))]
+    #[test_case(rust_type!({"type": "boolean"}), rust_type!("false") => rust_type!(false))]
+    #[test_case(rust_type!({"type": "boolean"}), rust_type!("no") => rust_type!("no"))]
+    #[test_case(rust_type!({"type": "null"}), rust_type!("null") => rust_type!(null))]
+    #[test_case(rust_type!({"type": "array"}), rust_type!(1) => rust_type!([1]))]
+    #[test_case(rust_type!({"type": "array"}), rust_type!({}) => rust_type!({}))]
+    #[test_case(rust_type!({"type": ["boolean", "integer"]}), rust_type!("1") => rust_type!(1))]
+    #[test_case(rust_type!({"type": ["string", "integer"]}), rust_type!("1") => rust_type!("1"))]
+    fn coerce(raw_schema: RustType, value: RustType) -> RustType {
+        let schema = draft4_schema(raw_schema).expect("Schema is supposed to be valid");
+        schema.do_on_validator(KeywordType::Type, &|maybe_draft_validator| {
+            let mut coerced_value = value.clone();
+            maybe_draft_validator
+                .expect("Expected validator to be found and of correct type")
+                .coerce(&mut coerced_value);
+            coerced_value
+        })
+    }
 }
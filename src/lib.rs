@@ -19,7 +19,6 @@
     private_in_public,
     unreachable_code,
     unreachable_patterns,
-    unsafe_code,
     unused_allocation,
     unused_assignments,
     unused_comparisons,
@@ -37,14 +36,8 @@
 )]
 // Enable very pendantic clippy linting
 #![deny(clippy::pedantic, clippy::nursery)]
-// Enable generator features (crate::iterator_utils::generator_iterator)
-#![feature(generators, generator_trait)]
-// Enable Arc::get_mut_unchecked for crate::types::scope_builder::ScopeBuilder::build
-#![feature(get_mut_unchecked)]
-// Enable str::strip_suffix for crate::types::validation_error::normalise_path
-#![feature(str_strip)]
-// Enable is_empty() on ExactSizeIterator instances
-#![feature(exact_size_is_empty)]
+// Compiled schemas are shared across threads, Send and Sync have to be guaranteed by construction
+#![forbid(unsafe_code)]
 #![allow(dead_code)] // TODO: Remove this. This is a temporary patch to allow existence of unused types
 
 // Macros have to be imported first so they will be fully available in the library
@@ -54,6 +47,55 @@ pub(in crate) mod testing_helpers;
 #[macro_use]
 extern crate strum_macros;
 
+pub(in crate) mod formats;
 pub(in crate) mod iterator_utils;
 pub(in crate) mod keywords;
+pub(in crate) mod lints;
 pub(in crate) mod types;
+
+pub use crate::types::{
+    annotation::Annotation, custom_keyword::CustomKeyword, draft_version::DraftVersion, instance::Instance, json_schema::JsonSchema, keyword_type::KeywordType,
+    lint_diagnostic::LintDiagnostic, lint_rule::LintRule, linter::Linter, openapi_context::OpenApiContext, schema::Schema, schema_error::SchemaError,
+    schema_warning::SchemaWarning, scope::Scope, scope_builder::ScopeBuilder, strict_mode::StrictMode, type_generator::TypeGenerator, validation_error::ValidationError,
+    validator_error_iterator::ValidationErrorIterator, vocabulary::Vocabulary,
+};
+
+#[cfg(feature = "serde_json")]
+pub use crate::formats::ndjson_::{NdjsonRecord, NdjsonValidator};
+
+#[cfg(feature = "derive")]
+pub use jsonschema_validator_derive::JsonSchema;
+
+// Items used by the code generated by `#[derive(JsonSchema)]` and by the `jsonschema-validator-macros` macros
+#[doc(hidden)]
+pub mod macro_support {
+    pub use crate::types::json_schema::{
+        adjacently_tagged_schema, externally_tagged_schema, flatten_schema, internally_tagged_schema, named_schema, nullable_schema, object_schema, one_of_schema,
+        string_enum_schema, tuple_schema,
+    };
+    pub use json_trait_rs::RustType;
+    pub use once_cell::sync::OnceCell;
+
+    use crate::{formats::compile_schema, lints::child_url, DraftVersion, Schema, SchemaError};
+    use loader_rs::loaders::RustTypeLoader;
+    use std::sync::Arc;
+    use url::Url;
+
+    /// Embedded schemas are compiled in the same way at build time and at runtime, so references to other documents are not resolved.
+    ///
+    /// # Errors
+    ///
+    /// The schema is malformed or it references other documents.
+    pub fn compile_embedded_schema(draft_version: DraftVersion, raw_schema: RustType) -> Result<Arc<Schema>, SchemaError> {
+        compile_schema(draft_version, RustTypeLoader::default(), raw_schema)
+    }
+
+    /// JSON pointer of the member of the value located by pointer, escaped as the fragments of the schema paths (ie. of `SchemaError::Malformed`).
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // The parsed url is constant
+    pub fn child_pointer(pointer: &str, member: &str) -> String {
+        let mut url = Url::parse("memory://schema").unwrap();
+        url.set_fragment(Some(pointer));
+        child_url(&url, &[member]).fragment().unwrap_or("").to_string()
+    }
+}
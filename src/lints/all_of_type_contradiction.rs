@@ -0,0 +1,48 @@
+use crate::{
+    lints::{declared_types, intersect_types},
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::JsonType;
+use url::Url;
+
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    let all_of = if let Some(value) = raw_schema.get_attribute("allOf").and_then(|value| value.as_array()) {
+        value
+    } else {
+        // allOf attribute is not there so we're done here
+        return Vec::new();
+    };
+
+    let common_types = all_of
+        .filter_map(declared_types)
+        .fold(declared_types(raw_schema), |common_types, branch_types| match common_types {
+            Some(common_types) => Some(intersect_types(&common_types, &branch_types)),
+            None => Some(branch_types),
+        });
+
+    if common_types.map_or(false, |common_types| common_types.is_empty()) {
+        vec![LintDiagnostic::new(
+            LintRule::AllOfTypeContradiction,
+            path,
+            "`type` of the schema and of its `allOf` schemas have no type in common",
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"allOf": [{"type": "number"}, {"type": "integer"}]}) => 0)]
+    #[test_case(rust_type!({"type": ["string", "null"], "allOf": [{"type": "null"}, {"minLength": 1}]}) => 0)]
+    #[test_case(rust_type!({"allOf": [{"type": "string"}, {"type": "integer"}]}) => 1)]
+    #[test_case(rust_type!({"type": "boolean", "allOf": [{"type": ["string", "integer"]}]}) => 1)]
+    fn test_check(raw_schema: RustType) -> usize {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema).len()
+    }
+}
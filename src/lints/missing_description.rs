@@ -0,0 +1,46 @@
+use crate::{
+    lints::child_url,
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::{JsonMapTrait, JsonType};
+use url::Url;
+
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    let properties = if let Some(value) = raw_schema.get_attribute("properties").and_then(|value| value.as_object()) {
+        value
+    } else {
+        // properties attribute is not there so we're done here
+        return Vec::new();
+    };
+
+    properties
+        .items()
+        // Referenced schemas are expected to carry their own description
+        .filter(|(_, property_schema)| property_schema.is_object() && property_schema.get_attribute("description").is_none() && property_schema.get_attribute("$ref").is_none())
+        .map(|(property_name, _)| {
+            LintDiagnostic::new(
+                LintRule::MissingDescription,
+                &child_url(path, &["properties", property_name]),
+                &format!("Property `{}` has no `description`", property_name),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"type": "object"}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"properties": {"a": {"description": "A"}, "b": {"$ref": "#/definitions/b"}}}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"properties": {"a": {"description": "A"}, "c": {"type": "string"}}}) => vec!["memory://schema#/properties/c".to_string()])]
+    fn test_check(raw_schema: RustType) -> Vec<String> {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema)
+            .iter()
+            .map(|diagnostic| diagnostic.path().to_string())
+            .collect()
+    }
+}
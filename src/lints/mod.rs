@@ -0,0 +1,210 @@
+pub(in crate) mod all_of_type_contradiction;
+pub(in crate) mod missing_description;
+pub(in crate) mod required_forbidden_property;
+pub(in crate) mod unreachable_one_of_branch;
+pub(in crate) mod unsatisfiable_range;
+pub(in crate) mod unused_definition;
+
+use crate::{
+    keywords::ref_::full_uri,
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType};
+use std::{collections::HashSet, convert::TryFrom};
+use url::Url;
+
+// Keywords whose value is a schema, an array of schemas or a map of schemas
+const SCHEMA_KEYWORDS: &[&str] = &["additionalItems", "additionalProperties", "items", "not"];
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf"];
+const SCHEMA_MAP_KEYWORDS: &[&str] = &["definitions", "dependencies", "patternProperties", "properties"];
+
+fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+pub(in crate) fn child_url(path: &Url, segments: &[&str]) -> Url {
+    let pointer = segments.iter().fold(path.fragment().unwrap_or("").to_string(), |pointer, segment| {
+        format!("{}/{}", pointer, escape_pointer_segment(segment))
+    });
+    let mut url = path.clone();
+    url.set_fragment(Some(&pointer));
+    url
+}
+
+// Visit the schema and all its subschemas, each with its own Url
+pub(in crate) fn walk_schema<J: JsonType>(path: &Url, raw_schema: &J, visitor: &mut dyn FnMut(&Url, &J)) {
+    if !raw_schema.is_object() {
+        // boolean values (ie. `additionalProperties: false`) and dependency arrays are not schemas
+        return;
+    }
+
+    visitor(path, raw_schema);
+    for keyword in SCHEMA_KEYWORDS {
+        if let Some(subschema) = raw_schema.get_attribute(keyword) {
+            walk_schema(&child_url(path, &[keyword]), subschema, visitor);
+        }
+    }
+    for keyword in SCHEMA_ARRAY_KEYWORDS {
+        if let Some(subschemas) = raw_schema.get_attribute(keyword).and_then(|value| value.as_array()) {
+            for (index, subschema) in subschemas.enumerate() {
+                walk_schema(&child_url(path, &[keyword, &index.to_string()]), subschema, visitor);
+            }
+        }
+    }
+    for keyword in SCHEMA_MAP_KEYWORDS {
+        if let Some(subschemas) = raw_schema.get_attribute(keyword).and_then(|value| value.as_object()) {
+            for (name, subschema) in subschemas.items() {
+                walk_schema(&child_url(path, &[keyword, name]), subschema, visitor);
+            }
+        }
+    }
+}
+
+// Urls referenced (via `$ref`) by the schema and all its subschemas
+pub(in crate) fn references<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<Url> {
+    let mut referenced_urls = Vec::new();
+    walk_schema(path, raw_schema, &mut |schema_path, schema| {
+        if let Some(json_reference) = schema.get_attribute("$ref").and_then(|value| value.as_string()) {
+            referenced_urls.push(full_uri(schema_path, json_reference));
+        }
+    });
+    referenced_urls
+}
+
+// Whether the schema located by `path` is the schema located by `root_path` or one of its subschemas
+pub(in crate) fn is_within(root_path: &Url, path: &Url) -> bool {
+    let (mut root_document, mut document) = (root_path.clone(), path.clone());
+    root_document.set_fragment(None);
+    document.set_fragment(None);
+    let (root_pointer, pointer) = (root_path.fragment().unwrap_or(""), path.fragment().unwrap_or(""));
+    root_document == document && (pointer == root_pointer || pointer.starts_with(&format!("{}/", root_pointer)))
+}
+
+// Types allowed by the `type` keyword, None if the keyword is not there (or is malformed)
+pub(in crate) fn declared_types<J: JsonType>(raw_schema: &J) -> Option<HashSet<PrimitiveType>> {
+    let type_attribute = raw_schema.get_attribute("type")?;
+    if let Some(type_str) = type_attribute.as_string() {
+        PrimitiveType::try_from(type_str).ok().map(|primitive_type| Some(primitive_type).into_iter().collect())
+    } else {
+        type_attribute
+            .as_array()?
+            .map(|item| item.as_string().and_then(|type_str| PrimitiveType::try_from(type_str).ok()))
+            .collect()
+    }
+}
+
+// Integers are a subset of numbers, so `number` admits `integer` in the intersection
+pub(in crate) fn intersect_types(left: &HashSet<PrimitiveType>, right: &HashSet<PrimitiveType>) -> HashSet<PrimitiveType> {
+    let expand = |types: &HashSet<PrimitiveType>| {
+        let mut expanded_types = types.clone();
+        if types.contains(&PrimitiveType::Number) {
+            let _ = expanded_types.insert(PrimitiveType::Integer);
+        }
+        expanded_types
+    };
+    expand(left).intersection(&expand(right)).cloned().collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(in crate) fn as_f64<J: JsonType>(value: &J) -> Option<f64> {
+    value.as_number().or_else(|| value.as_integer().map(|integer| integer as f64))
+}
+
+pub(in crate) fn check_schema<J: JsonType>(rule: LintRule, path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    match rule {
+        LintRule::UnsatisfiableRange => unsatisfiable_range::check(path, raw_schema),
+        LintRule::RequiredForbiddenProperty => required_forbidden_property::check(path, raw_schema),
+        LintRule::UnreachableOneOfBranch => unreachable_one_of_branch::check(path, raw_schema),
+        LintRule::AllOfTypeContradiction => all_of_type_contradiction::check(path, raw_schema),
+        LintRule::MissingDescription => missing_description::check(path, raw_schema),
+        // Definitions are used across the whole document, so they are checked by unused_definition::check
+        LintRule::UnusedDefinition => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{child_url, declared_types, intersect_types, is_within, references, walk_schema};
+    use crate::hash_set;
+    use json_trait_rs::{rust_type, PrimitiveType};
+    use std::collections::HashSet;
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case("memory://schema", &["properties", "name"] => "memory://schema#/properties/name")]
+    #[test_case("memory://schema#/definitions/a", &["items", "0"] => "memory://schema#/definitions/a/items/0")]
+    #[test_case("memory://schema#", &["properties", "a/b~c"] => "memory://schema#/properties/a~1b~0c")]
+    fn test_child_url(path: &str, segments: &[&str]) -> String {
+        child_url(&Url::parse(path).unwrap(), segments).to_string()
+    }
+
+    #[test]
+    fn test_walk_schema() {
+        let mut visited_urls = Vec::new();
+        walk_schema(
+            &Url::parse("memory://schema").unwrap(),
+            &rust_type!({"properties": {"a": {"items": [{}, {}]}}, "additionalProperties": false, "allOf": [{"not": {}}]}),
+            &mut |path, _| visited_urls.push(path.to_string()),
+        );
+        visited_urls.sort();
+        assert_eq!(
+            visited_urls,
+            vec![
+                "memory://schema",
+                "memory://schema#/allOf/0",
+                "memory://schema#/allOf/0/not",
+                "memory://schema#/properties/a",
+                "memory://schema#/properties/a/items/0",
+                "memory://schema#/properties/a/items/1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let raw_schema = rust_type!({
+            "properties": {"a": {"$ref": "#/definitions/a"}, "b": {"items": {"$ref": "other.json#/definitions/b"}}},
+            "definitions": {"a": {"$ref": "#"}},
+        });
+        let mut referenced_urls = references(&Url::parse("memory://folder/schema.json").unwrap(), &raw_schema)
+            .iter()
+            .map(Url::to_string)
+            .collect::<Vec<_>>();
+        referenced_urls.sort();
+        assert_eq!(
+            referenced_urls,
+            vec![
+                "memory://folder/other.json#/definitions/b",
+                "memory://folder/schema.json#",
+                "memory://folder/schema.json#/definitions/a",
+            ]
+        );
+    }
+
+    #[test_case("memory://schema", "memory://schema#" => true)]
+    #[test_case("memory://schema", "memory://schema#/definitions/a" => true)]
+    #[test_case("memory://schema#/definitions/a", "memory://schema#/definitions/a/items" => true)]
+    #[test_case("memory://schema#/definitions/a", "memory://schema#/definitions/ab" => false)]
+    #[test_case("memory://schema#/definitions/a", "memory://schema" => false)]
+    #[test_case("memory://schema", "memory://other#/definitions/a" => false)]
+    fn test_is_within(root_path: &str, path: &str) -> bool {
+        is_within(&Url::parse(root_path).unwrap(), &Url::parse(path).unwrap())
+    }
+
+    #[test_case(&hash_set![PrimitiveType::Number], &hash_set![PrimitiveType::Integer] => hash_set![PrimitiveType::Integer])]
+    #[test_case(&hash_set![PrimitiveType::String, PrimitiveType::Null], &hash_set![PrimitiveType::Null] => hash_set![PrimitiveType::Null])]
+    #[test_case(&hash_set![PrimitiveType::String], &hash_set![PrimitiveType::Integer] => hash_set![])]
+    fn test_intersect_types(left: &HashSet<PrimitiveType>, right: &HashSet<PrimitiveType>) -> HashSet<PrimitiveType> {
+        intersect_types(left, right)
+    }
+
+    #[test]
+    fn test_declared_types() {
+        assert_eq!(
+            declared_types(&rust_type!({"type": ["string", "null"]})),
+            Some(hash_set![PrimitiveType::String, PrimitiveType::Null])
+        );
+        assert_eq!(declared_types(&rust_type!({"type": "not-real-type"})), None);
+        assert_eq!(declared_types(&rust_type!({})), None);
+    }
+}
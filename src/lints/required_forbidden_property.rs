@@ -0,0 +1,56 @@
+use crate::types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule};
+use json_trait_rs::JsonType;
+use url::Url;
+
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    // Properties matching patternProperties are allowed as well, but patterns are not evaluated by the linter
+    if raw_schema.get_attribute("additionalProperties").and_then(|value| value.as_boolean()) != Some(false) || raw_schema.get_attribute("patternProperties").is_some() {
+        return Vec::new();
+    }
+
+    let required = if let Some(value) = raw_schema.get_attribute("required").and_then(|value| value.as_array()) {
+        value
+    } else {
+        // required attribute is not there so we're done here
+        return Vec::new();
+    };
+
+    required
+        .filter_map(|item| item.as_string())
+        .filter(|property_name| {
+            raw_schema
+                .get_attribute("properties")
+                .and_then(|properties| properties.get_attribute(property_name))
+                .is_none()
+        })
+        .map(|property_name| {
+            LintDiagnostic::new(
+                LintRule::RequiredForbiddenProperty,
+                path,
+                &format!("Required property `{}` is not allowed by `additionalProperties: false`", property_name),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"required": ["a"]}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"required": ["a"], "properties": {"a": {}}, "additionalProperties": false}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"required": ["b"], "patternProperties": {"^b": {}}, "additionalProperties": false}) => Vec::<String>::new())]
+    #[test_case(
+        rust_type!({"required": ["a", "b"], "properties": {"a": {}}, "additionalProperties": false})
+        => vec!["Required property `b` is not allowed by `additionalProperties: false`".to_string()]
+    )]
+    fn test_check(raw_schema: RustType) -> Vec<String> {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    lints::{child_url, declared_types, intersect_types},
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::{JsonType, RustType};
+use url::Url;
+
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    let branches = if let Some(value) = raw_schema.get_attribute("oneOf").and_then(|value| value.as_array()) {
+        value.collect::<Vec<_>>()
+    } else {
+        // oneOf attribute is not there so we're done here
+        return Vec::new();
+    };
+    let rust_type_branches = branches.iter().map(|branch| branch.to_rust_type()).collect::<Vec<RustType>>();
+    let parent_types = declared_types(raw_schema);
+
+    branches
+        .iter()
+        .enumerate()
+        .filter_map(|(index, branch)| {
+            // An instance valid against a duplicated branch is valid against (at least) two branches
+            let is_duplicated = rust_type_branches
+                .iter()
+                .enumerate()
+                .any(|(other_index, other_branch)| other_index != index && other_branch == &rust_type_branches[index]);
+            let is_excluded_by_type = match (&parent_types, declared_types(*branch)) {
+                (Some(parent_types), Some(branch_types)) => intersect_types(parent_types, &branch_types).is_empty(),
+                _ => false,
+            };
+
+            let message = if is_duplicated {
+                "Branch is identical to another `oneOf` branch"
+            } else if is_excluded_by_type {
+                "Branch `type` is not allowed by the parent schema `type`"
+            } else {
+                return None;
+            };
+            Some(LintDiagnostic::new(
+                LintRule::UnreachableOneOfBranch,
+                &child_url(path, &["oneOf", &index.to_string()]),
+                message,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"oneOf": [{"type": "string"}, {"type": "integer"}]}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"type": "number", "oneOf": [{"type": "integer"}, {"minimum": 1}]}) => Vec::<String>::new())]
+    #[test_case(
+        rust_type!({"oneOf": [{"type": "string"}, {"type": "integer"}, {"type": "string"}]})
+        => vec!["memory://schema#/oneOf/0".to_string(), "memory://schema#/oneOf/2".to_string()]
+    )]
+    #[test_case(rust_type!({"type": "string", "oneOf": [{"type": "string"}, {"type": "boolean"}]}) => vec!["memory://schema#/oneOf/1".to_string()])]
+    fn test_check(raw_schema: RustType) -> Vec<String> {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema)
+            .iter()
+            .map(|diagnostic| diagnostic.path().to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    lints::as_f64,
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::JsonType;
+use url::Url;
+
+// (lower bound keyword, upper bound keyword)
+const RANGES: &[(&str, &str)] = &[
+    ("minimum", "maximum"),
+    ("minLength", "maxLength"),
+    ("minItems", "maxItems"),
+    ("minProperties", "maxProperties"),
+];
+
+fn is_exclusive<J: JsonType>(raw_schema: &J, keyword: &str) -> bool {
+    raw_schema.get_attribute(keyword).and_then(|value| value.as_boolean()) == Some(true)
+}
+
+#[allow(clippy::float_cmp)]
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    RANGES
+        .iter()
+        .filter_map(|(lower_keyword, upper_keyword)| {
+            let lower_bound = raw_schema.get_attribute(lower_keyword).and_then(as_f64)?;
+            let upper_bound = raw_schema.get_attribute(upper_keyword).and_then(as_f64)?;
+            // Draft4 exclusive bounds are booleans modifying minimum and maximum
+            let is_empty_range =
+                lower_bound == upper_bound && *lower_keyword == "minimum" && (is_exclusive(raw_schema, "exclusiveMinimum") || is_exclusive(raw_schema, "exclusiveMaximum"));
+            if lower_bound > upper_bound || is_empty_range {
+                Some(LintDiagnostic::new(
+                    LintRule::UnsatisfiableRange,
+                    path,
+                    &format!("`{}` ({}) and `{}` ({}) cannot be both satisfied", lower_keyword, lower_bound, upper_keyword, upper_bound),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"minimum": 1, "maximum": 2}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"minimum": 2, "maximum": 2}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"minimum": 3, "maximum": 2.5}) => vec!["`minimum` (3) and `maximum` (2.5) cannot be both satisfied".to_string()])]
+    #[test_case(rust_type!({"minimum": 2, "maximum": 2, "exclusiveMaximum": true}) => vec!["`minimum` (2) and `maximum` (2) cannot be both satisfied".to_string()])]
+    #[test_case(rust_type!({"minLength": 5, "maxLength": 1}) => vec!["`minLength` (5) and `maxLength` (1) cannot be both satisfied".to_string()])]
+    fn test_check(raw_schema: RustType) -> Vec<String> {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    keywords::ref_::full_uri,
+    lints::{child_url, walk_schema},
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::{JsonMapTrait, JsonType};
+use url::Url;
+
+// Definitions are checked on the document root only, as references could point to any part of the document
+pub(in crate) fn check<J: JsonType>(path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+    let definitions = if let Some(value) = raw_schema.get_attribute("definitions").and_then(|value| value.as_object()) {
+        value
+    } else {
+        // definitions attribute is not there so we're done here
+        return Vec::new();
+    };
+
+    let mut referenced_urls = Vec::new();
+    walk_schema(path, raw_schema, &mut |schema_path, schema| {
+        if let Some(reference) = schema.get_attribute("$ref").and_then(|value| value.as_string()) {
+            referenced_urls.push(full_uri(schema_path, reference).to_string());
+        }
+    });
+
+    definitions
+        .items()
+        .filter_map(|(definition_name, _)| {
+            let definition_url = child_url(path, &["definitions", definition_name]);
+            let nested_definition_prefix = format!("{}/", definition_url);
+            if referenced_urls
+                .iter()
+                .any(|referenced_url| referenced_url == definition_url.as_str() || referenced_url.starts_with(&nested_definition_prefix))
+            {
+                None
+            } else {
+                Some(LintDiagnostic::new(
+                    LintRule::UnusedDefinition,
+                    &definition_url,
+                    &format!("Definition `{}` is never referenced", definition_name),
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+    use url::Url;
+
+    #[test_case(rust_type!({"type": "object"}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"definitions": {"a": {}}, "properties": {"a": {"$ref": "#/definitions/a"}}}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"definitions": {"a": {"properties": {"b": {}}}}, "items": {"$ref": "#/definitions/a/properties/b"}}) => Vec::<String>::new())]
+    #[test_case(rust_type!({"definitions": {"a": {}, "ab": {}}, "$ref": "#/definitions/a"}) => vec!["memory://schema#/definitions/ab".to_string()])]
+    fn test_check(raw_schema: RustType) -> Vec<String> {
+        check(&Url::parse("memory://schema").unwrap(), &raw_schema)
+            .iter()
+            .map(|diagnostic| diagnostic.path().to_string())
+            .collect()
+    }
+}
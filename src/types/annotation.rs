@@ -0,0 +1,65 @@
+use crate::types::custom_keyword::CustomKeywordRegistration;
+use json_trait_rs::{JsonMapTrait, JsonType, RustType};
+use url::Url;
+
+// Keywords that do not assert anything on the instance, they are collected as they are
+const ANNOTATION_KEYWORDS: &[&str] = &["title", "description", "default", "examples", "example", "deprecated", "readOnly", "writeOnly"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    instance_location: String,
+    schema_location: Url,
+    keyword: String,
+    value: RustType,
+}
+
+impl Annotation {
+    #[must_use]
+    pub fn new(instance_location: &str, schema_location: &Url, keyword: &str, value: &RustType) -> Self {
+        Self {
+            instance_location: instance_location.to_string(),
+            schema_location: schema_location.clone(),
+            keyword: keyword.to_string(),
+            value: value.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn instance_location(&self) -> &str {
+        &self.instance_location
+    }
+
+    #[must_use]
+    pub const fn schema_location(&self) -> &Url {
+        &self.schema_location
+    }
+
+    #[must_use]
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> &RustType {
+        &self.value
+    }
+}
+
+// Annotations defined by the schema (subschemas excluded), sorted by keyword.
+// Unknown `x-` keywords are annotations as well, unless they are registered as custom keywords.
+pub(in crate) fn schema_annotations<J: JsonType>(raw_schema: &J, custom_keywords: &[CustomKeywordRegistration]) -> Vec<(String, RustType)> {
+    let schema_object = if let Some(value) = raw_schema.as_object() {
+        value
+    } else {
+        // non object schemas are reported by Schema::create so we're done here
+        return Vec::new();
+    };
+
+    let mut annotations = schema_object
+        .items()
+        .filter(|(keyword, _)| ANNOTATION_KEYWORDS.contains(keyword) || (keyword.starts_with("x-") && !custom_keywords.iter().any(|registration| registration.keyword == *keyword)))
+        .map(|(keyword, value)| (keyword.to_string(), value.to_rust_type()))
+        .collect::<Vec<_>>();
+    annotations.sort_by(|(left_keyword, _), (right_keyword, _)| left_keyword.cmp(right_keyword));
+    annotations
+}
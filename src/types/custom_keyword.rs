@@ -0,0 +1,53 @@
+use crate::types::{draft_version::DraftVersion, instance::Instance, schema_error::SchemaError, validator_error_iterator::ValidationErrorIterator};
+use json_trait_rs::RustType;
+use std::fmt::Debug;
+use url::Url;
+
+/// Keywords defined outside of the drafts, registered via `ScopeBuilder::register_custom_keyword`.
+///
+/// Custom keywords are stored as trait objects, so their methods cannot be generic over `JsonType`.
+/// Keyword values are converted to `RustType` during compilation, while instances are borrowed via `Instance` during validation.
+pub trait CustomKeyword: Debug + Send + Sync {
+    /// `Ok(None)` means that the keyword value does not constrain the instances, so no validator is needed.
+    ///
+    /// # Errors
+    ///
+    /// The keyword value is not valid, usually reported as `SchemaError::Malformed`.
+    fn compile(path: &Url, keyword_value: &RustType) -> Result<Option<Self>, SchemaError>
+    where
+        Self: Sized;
+
+    fn validation_errors(&self, path: &str, value: Instance<'_>) -> ValidationErrorIterator;
+
+    fn is_valid(&self, path: &str, value: Instance<'_>) -> bool {
+        self.validation_errors(path, value).next().is_none()
+    }
+}
+
+type CompileCustomKeyword = fn(&Url, &RustType) -> Result<Option<Box<dyn CustomKeyword>>, SchemaError>;
+
+fn compile_custom_keyword<K: 'static + CustomKeyword>(path: &Url, keyword_value: &RustType) -> Result<Option<Box<dyn CustomKeyword>>, SchemaError> {
+    Ok(K::compile(path, keyword_value)?.map(|custom_keyword| Box::new(custom_keyword) as Box<dyn CustomKeyword>))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(in crate) struct CustomKeywordRegistration {
+    pub(in crate) keyword: &'static str,
+    // None means that the keyword is available on all the drafts
+    pub(in crate) draft_version: Option<DraftVersion>,
+    pub(in crate) compile: CompileCustomKeyword,
+}
+
+impl CustomKeywordRegistration {
+    pub(in crate) fn new<K: 'static + CustomKeyword>(keyword: &'static str, draft_version: Option<DraftVersion>) -> Self {
+        Self {
+            keyword,
+            draft_version,
+            compile: compile_custom_keyword::<K>,
+        }
+    }
+
+    pub(in crate) fn is_enabled_for(&self, draft_version: DraftVersion) -> bool {
+        self.draft_version.map_or(true, |registered_draft_version| registered_draft_version == draft_version)
+    }
+}
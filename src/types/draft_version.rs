@@ -1,7 +1,11 @@
+/// JSON Schema specification used to compile the schemas.
 #[allow(clippy::pub_enum_variant_names)]
 #[derive(Clone, Copy, EnumIter, Debug, Display, PartialEq)]
-pub(in crate) enum DraftVersion {
+pub enum DraftVersion {
+    /// [JSON Schema Draft 4](https://tools.ietf.org/html/draft-zyp-json-schema-04)
     Draft4,
+    /// [OpenAPI 3.0 Schema Object](https://spec.openapis.org/oas/v3.0.3#schema-object)
+    OpenApi30,
 }
 
 impl Default for DraftVersion {
@@ -0,0 +1,147 @@
+use json_trait_rs::{JsonMapTrait, JsonType, PrimitiveType, RustType};
+use std::fmt::Debug;
+
+// Object safe subset of JsonType (JsonType has generic methods), so custom keywords could be stored as trait objects
+// and still inspect instances of any JsonType without copying them
+trait ErasedJsonType: Debug {
+    fn primitive_type(&self) -> PrimitiveType;
+    fn as_boolean(&self) -> Option<bool>;
+    fn as_integer(&self) -> Option<i128>;
+    fn as_number(&self) -> Option<f64>;
+    fn as_string(&self) -> Option<&str>;
+    fn items(&self) -> Option<Vec<&dyn ErasedJsonType>>;
+    fn property_names(&self) -> Option<Vec<String>>;
+    fn get_attribute(&self, attribute_name: &str) -> Option<&dyn ErasedJsonType>;
+    fn to_rust_type(&self) -> RustType;
+}
+
+impl<T: JsonType> ErasedJsonType for T {
+    fn primitive_type(&self) -> PrimitiveType {
+        JsonType::primitive_type(self)
+    }
+
+    fn as_boolean(&self) -> Option<bool> {
+        JsonType::as_boolean(self)
+    }
+
+    fn as_integer(&self) -> Option<i128> {
+        JsonType::as_integer(self)
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        JsonType::as_number(self)
+    }
+
+    fn as_string(&self) -> Option<&str> {
+        JsonType::as_string(self)
+    }
+
+    fn items(&self) -> Option<Vec<&dyn ErasedJsonType>> {
+        JsonType::as_array(self).map(|items| items.map(|item| item as &dyn ErasedJsonType).collect())
+    }
+
+    fn property_names(&self) -> Option<Vec<String>> {
+        JsonType::as_object(self).map(|object| object.items().map(|(property_name, _)| property_name.to_string()).collect())
+    }
+
+    fn get_attribute(&self, attribute_name: &str) -> Option<&dyn ErasedJsonType> {
+        JsonType::get_attribute(self, attribute_name).map(|value| value as &dyn ErasedJsonType)
+    }
+
+    fn to_rust_type(&self) -> RustType {
+        JsonType::to_rust_type(self)
+    }
+}
+
+/// Instance validated by a `CustomKeyword`, borrowed from the validated value (whatever its `JsonType` is).
+///
+/// Nothing is copied unless the keyword asks for it via `to_rust_type`.
+#[derive(Clone, Copy, Debug)]
+pub struct Instance<'json>(&'json dyn ErasedJsonType);
+
+impl<'json> Instance<'json> {
+    pub(in crate) fn new<T: JsonType>(value: &'json T) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub fn primitive_type(&self) -> PrimitiveType {
+        self.0.primitive_type()
+    }
+
+    #[must_use]
+    pub fn as_boolean(&self) -> Option<bool> {
+        self.0.as_boolean()
+    }
+
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i128> {
+        self.0.as_integer()
+    }
+
+    #[must_use]
+    pub fn as_number(&self) -> Option<f64> {
+        self.0.as_number()
+    }
+
+    #[must_use]
+    pub fn as_string(&self) -> Option<&'json str> {
+        self.0.as_string()
+    }
+
+    /// Items of the instance, if it is an array.
+    #[must_use]
+    pub fn items(&self) -> Option<Vec<Instance<'json>>> {
+        self.0.items().map(|items| items.into_iter().map(Instance).collect())
+    }
+
+    /// Property names of the instance, if it is an object.
+    #[must_use]
+    pub fn property_names(&self) -> Option<Vec<String>> {
+        self.0.property_names()
+    }
+
+    /// Value of the property, if the instance is an object containing it.
+    #[must_use]
+    pub fn get_attribute(&self, attribute_name: &str) -> Option<Instance<'json>> {
+        self.0.get_attribute(attribute_name).map(Instance)
+    }
+
+    /// Copy of the whole instance, meant for keywords that could not be evaluated otherwise.
+    #[must_use]
+    pub fn to_rust_type(&self) -> RustType {
+        self.0.to_rust_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instance;
+    use json_trait_rs::{rust_type, PrimitiveType};
+
+    #[test]
+    fn inspect_instance() {
+        let value = rust_type!({"name": "n", "tags": [1, 2.5, true, null]});
+        let instance = Instance::new(&value);
+        assert_eq!(instance.primitive_type(), PrimitiveType::Object);
+        assert_eq!(
+            instance.property_names().map(|mut property_names| {
+                property_names.sort();
+                property_names
+            }),
+            Some(vec!["name".to_string(), "tags".to_string()])
+        );
+        assert_eq!(instance.get_attribute("name").and_then(|name| name.as_string()), Some("n"));
+        assert!(instance.get_attribute("missing").is_none());
+
+        let tags = instance.get_attribute("tags").and_then(|tags| tags.items()).expect("tags is supposed to be an array");
+        assert_eq!(
+            tags.iter().map(Instance::primitive_type).collect::<Vec<_>>(),
+            vec![PrimitiveType::Integer, PrimitiveType::Number, PrimitiveType::Boolean, PrimitiveType::Null]
+        );
+        assert_eq!(tags[0].as_integer(), Some(1));
+        assert_eq!(tags[1].as_number(), Some(2.5));
+        assert_eq!(tags[2].as_boolean(), Some(true));
+        assert_eq!(instance.to_rust_type(), value);
+    }
+}
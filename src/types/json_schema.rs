@@ -0,0 +1,351 @@
+use crate::{
+    formats::compile_schema,
+    types::{draft_version::DraftVersion, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder},
+};
+use json_trait_rs::RustType;
+use loader_rs::loaders::RustTypeLoader;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    mem,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Types describing their own JSON representation, usually implemented via `#[derive(JsonSchema)]` (`derive` feature).
+/// Schemas could use keywords that are not validated yet (ie. `enum`, `oneOf`, `items` and `required`).
+/// Recursive derived types are referenced via `$ref`, so their schemas are finite.
+pub trait JsonSchema {
+    fn json_schema() -> RustType;
+
+    /// Compile the schema of the type, so untrusted input could be validated before deserialising it.
+    ///
+    /// # Errors
+    ///
+    /// The schema returned by `json_schema` is malformed.
+    fn compiled_schema() -> Result<Arc<Schema>, SchemaError> {
+        compile_schema(DraftVersion::Draft4, RustTypeLoader::default(), Self::json_schema())
+    }
+}
+
+impl ScopeBuilder<RustType> {
+    /// Compile the schema of S within the scope, the schema is not retrievable by other schemas.
+    ///
+    /// # Errors
+    ///
+    /// The schema returned by `S::json_schema` is malformed.
+    pub fn json_schema<S: JsonSchema>(&mut self) -> Result<Arc<Schema>, SchemaError> {
+        let raw_schema = Arc::new(S::json_schema());
+        let generated_url = self.inject_schema(&raw_schema);
+        self.schema(&generated_url, &*raw_schema)
+    }
+}
+
+fn object(entries: Vec<(&str, RustType)>) -> RustType {
+    RustType::Object(entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+}
+
+fn string(value: &str) -> RustType {
+    RustType::String(value.to_string())
+}
+
+fn typed(type_name: &str) -> RustType {
+    object(vec![("type", string(type_name))])
+}
+
+fn reference(json_reference: &str) -> RustType {
+    object(vec![("$ref", string(json_reference))])
+}
+
+// State of the derived schemas being generated on the current thread, it is reset once the outermost schema is generated
+#[derive(Default)]
+struct SchemaGeneration {
+    // Types whose schema is being generated, the outermost first
+    type_names: Vec<&'static str>,
+    recursive_type_names: HashSet<&'static str>,
+    // Definition names are unique, even if types with the same name (ie. generic types) are defined
+    definition_names: HashMap<&'static str, String>,
+    definitions: Vec<(String, RustType)>,
+}
+
+impl SchemaGeneration {
+    fn definition_name(&mut self, type_name: &'static str, name: &str) -> String {
+        if let Some(definition_name) = self.definition_names.get(type_name) {
+            return definition_name.clone();
+        }
+        let definition_name = (1..)
+            .map(|index| if index == 1 { name.to_string() } else { format!("{}{}", name, index) })
+            .find(|definition_name| !self.definition_names.values().any(|other_name| other_name == definition_name))
+            .unwrap();
+        let _ = self.definition_names.insert(type_name, definition_name.clone());
+        definition_name
+    }
+
+    // Reference to the schema of a type being generated, the outermost type is the root of the schema
+    fn recursive_reference(&mut self, type_name: &'static str, name: &str) -> RustType {
+        let _ = self.recursive_type_names.insert(type_name);
+        if self.type_names.first() == Some(&type_name) {
+            reference("#")
+        } else {
+            reference(&format!("#/definitions/{}", self.definition_name(type_name, name)))
+        }
+    }
+}
+
+thread_local! {
+    static SCHEMA_GENERATION: RefCell<SchemaGeneration> = RefCell::new(SchemaGeneration::default());
+}
+
+// Helpers used by the code generated by `#[derive(JsonSchema)]`, they are not meant to be used directly
+#[must_use]
+pub fn object_schema(properties: Vec<(&str, RustType, bool)>, deny_unknown_fields: bool) -> RustType {
+    let required = properties
+        .iter()
+        .filter(|(_, _, is_required)| *is_required)
+        .map(|(name, _, _)| string(name))
+        .collect::<Vec<_>>();
+    let mut entries = vec![
+        ("type", string("object")),
+        ("properties", object(properties.into_iter().map(|(name, schema, _)| (name, schema)).collect())),
+    ];
+    if !required.is_empty() {
+        entries.push(("required", RustType::List(required)));
+    }
+    if deny_unknown_fields {
+        entries.push(("additionalProperties", RustType::Boolean(false)));
+    }
+    object(entries)
+}
+
+// Merge `properties` and `required` of flattened_schema into schema (`#[serde(flatten)]`)
+pub fn flatten_schema(schema: &mut RustType, flattened_schema: RustType) {
+    if let (RustType::Object(schema), RustType::Object(mut flattened_schema)) = (schema, flattened_schema) {
+        for keyword in &["properties", "required"] {
+            match (schema.get_mut(*keyword), flattened_schema.remove(*keyword)) {
+                (Some(RustType::Object(properties)), Some(RustType::Object(flattened_properties))) => properties.extend(flattened_properties),
+                (Some(RustType::List(required)), Some(RustType::List(flattened_required))) => required.extend(flattened_required),
+                (None, Some(flattened_value)) => {
+                    let _ = schema.insert((*keyword).to_string(), flattened_value);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Schema of a derived type, recursive types are described once in `definitions` (or by the root schema) and referenced via `$ref`
+#[must_use]
+pub fn named_schema(type_name: &'static str, name: &str, generate: impl FnOnce() -> RustType) -> RustType {
+    let recursive_reference = SCHEMA_GENERATION.with(|generation| {
+        let mut generation = generation.borrow_mut();
+        if generation.type_names.contains(&type_name) {
+            Some(generation.recursive_reference(type_name, name))
+        } else if generation.recursive_type_names.contains(type_name) && generation.definition_names.contains_key(type_name) {
+            // The type is already described in `definitions`
+            Some(reference(&format!("#/definitions/{}", generation.definition_name(type_name, name))))
+        } else {
+            generation.type_names.push(type_name);
+            None
+        }
+    });
+    if let Some(recursive_reference) = recursive_reference {
+        return recursive_reference;
+    }
+
+    let schema = generate();
+    SCHEMA_GENERATION.with(|generation| {
+        let mut generation = generation.borrow_mut();
+        let _ = generation.type_names.pop();
+        if generation.type_names.is_empty() {
+            let definitions = mem::take(&mut *generation).definitions;
+            match schema {
+                RustType::Object(mut schema) if !definitions.is_empty() => {
+                    let _ = schema.insert("definitions".to_string(), RustType::Object(definitions.into_iter().collect()));
+                    RustType::Object(schema)
+                }
+                schema => schema,
+            }
+        } else if generation.recursive_type_names.contains(type_name) {
+            let definition_name = generation.definition_name(type_name, name);
+            generation.definitions.push((definition_name.clone(), schema));
+            reference(&format!("#/definitions/{}", definition_name))
+        } else {
+            schema
+        }
+    })
+}
+
+// Allow null as well (`Option`)
+#[must_use]
+pub fn nullable_schema(mut schema: RustType) -> RustType {
+    let mut is_combined = false;
+    if let RustType::Object(object) = &mut schema {
+        if let Some(RustType::List(values)) = object.get_mut("enum") {
+            if !values.contains(&RustType::Null) {
+                values.push(RustType::Null);
+            }
+        }
+        match object.get_mut("type") {
+            Some(RustType::String(type_name)) => {
+                let type_name = type_name.clone();
+                let _ = object.insert("type".to_string(), RustType::List(vec![string(&type_name), string("null")]));
+            }
+            Some(RustType::List(type_names)) if !type_names.contains(&string("null")) => type_names.push(string("null")),
+            Some(_) => {}
+            // The combined (or referenced) schemas would reject null
+            None => is_combined = object.contains_key("oneOf") || object.contains_key("$ref"),
+        }
+    }
+    if is_combined {
+        one_of_schema(vec![schema, typed("null")])
+    } else {
+        schema
+    }
+}
+
+#[must_use]
+pub fn one_of_schema(schemas: Vec<RustType>) -> RustType {
+    object(vec![("oneOf", RustType::List(schemas))])
+}
+
+#[must_use]
+pub fn string_enum_schema(values: &[&str]) -> RustType {
+    object(vec![
+        ("type", string("string")),
+        ("enum", RustType::List(values.iter().map(|value| string(value)).collect())),
+    ])
+}
+
+#[must_use]
+pub fn tuple_schema(items: Vec<RustType>) -> RustType {
+    object(vec![("type", string("array")), ("items", RustType::List(items))])
+}
+
+// Externally tagged variant (serde default representation of enums), ie. `{"Variant": ...}`
+#[must_use]
+pub fn externally_tagged_schema(variant: &str, schema: RustType) -> RustType {
+    object_schema(vec![(variant, schema, true)], true)
+}
+
+// Internally tagged variant (`#[serde(tag = "...")]`), the tag is merged into the object schema of the variant
+#[must_use]
+pub fn internally_tagged_schema(tag: &str, variant: &str, schema: RustType) -> RustType {
+    let mut tagged_schema = object_schema(vec![(tag, string_enum_schema(&[variant]), true)], false);
+    flatten_schema(&mut tagged_schema, schema);
+    tagged_schema
+}
+
+// Adjacently tagged variant (`#[serde(tag = "...", content = "...")]`)
+#[must_use]
+pub fn adjacently_tagged_schema(tag: &str, content: &str, variant: &str, schema: Option<RustType>) -> RustType {
+    let mut properties = vec![(tag, string_enum_schema(&[variant]), true)];
+    if let Some(schema) = schema {
+        properties.push((content, schema, true));
+    }
+    object_schema(properties, true)
+}
+
+macro_rules! impl_json_schema {
+    ($type_name:literal => $($rust_type:ty),*) => {
+        $(
+            impl JsonSchema for $rust_type {
+                fn json_schema() -> RustType {
+                    typed($type_name)
+                }
+            }
+        )*
+    };
+}
+
+impl_json_schema!("boolean" => bool);
+impl_json_schema!("integer" => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_json_schema!("number" => f32, f64);
+impl_json_schema!("string" => char, str, String);
+impl_json_schema!("null" => ());
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    fn json_schema() -> RustType {
+        nullable_schema(T::json_schema())
+    }
+}
+
+macro_rules! impl_json_schema_for_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T: JsonSchema + ?Sized> JsonSchema for $wrapper<T> {
+                fn json_schema() -> RustType {
+                    T::json_schema()
+                }
+            }
+        )*
+    };
+}
+
+impl_json_schema_for_wrapper!(Box, Rc, Arc);
+
+macro_rules! impl_json_schema_for_sequence {
+    ($($sequence:ident),*) => {
+        $(
+            impl<T: JsonSchema> JsonSchema for $sequence<T> {
+                fn json_schema() -> RustType {
+                    object(vec![("type", string("array")), ("items", T::json_schema())])
+                }
+            }
+        )*
+    };
+}
+
+impl_json_schema_for_sequence!(Vec, HashSet, BTreeSet);
+
+impl<T: JsonSchema> JsonSchema for [T] {
+    fn json_schema() -> RustType {
+        Vec::<T>::json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for HashMap<String, T> {
+    fn json_schema() -> RustType {
+        object(vec![("type", string("object")), ("additionalProperties", T::json_schema())])
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeMap<String, T> {
+    fn json_schema() -> RustType {
+        HashMap::<String, T>::json_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flatten_schema, nullable_schema, JsonSchema};
+    use json_trait_rs::{rust_type, RustType};
+    use std::collections::HashMap;
+    use test_case::test_case;
+
+    #[test_case(rust_type!({"type": "string"}) => rust_type!({"type": ["string", "null"]}))]
+    #[test_case(rust_type!({"type": ["string", "integer"]}) => rust_type!({"type": ["string", "integer", "null"]}))]
+    #[test_case(rust_type!({"type": ["string", "null"]}) => rust_type!({"type": ["string", "null"]}))]
+    #[test_case(rust_type!({"type": "string", "enum": ["a"]}) => rust_type!({"type": ["string", "null"], "enum": ["a", null]}))]
+    #[test_case(rust_type!({"oneOf": []}) => rust_type!({"oneOf": [{"oneOf": []}, {"type": "null"}]}))]
+    #[test_case(rust_type!({"$ref": "#"}) => rust_type!({"oneOf": [{"$ref": "#"}, {"type": "null"}]}))]
+    fn test_nullable_schema(schema: RustType) -> RustType {
+        nullable_schema(schema)
+    }
+
+    #[test]
+    fn test_flatten_schema() {
+        let mut schema = rust_type!({"type": "object", "properties": {"a": {}}});
+        flatten_schema(&mut schema, rust_type!({"type": "object", "properties": {"b": {}}, "required": ["b"]}));
+        assert_eq!(schema, rust_type!({"type": "object", "properties": {"a": {}, "b": {}}, "required": ["b"]}));
+    }
+
+    #[test]
+    fn std_types_schemas() {
+        assert_eq!(Option::<Vec<u8>>::json_schema(), rust_type!({"type": ["array", "null"], "items": {"type": "integer"}}));
+        assert_eq!(
+            HashMap::<String, bool>::json_schema(),
+            rust_type!({"type": "object", "additionalProperties": {"type": "boolean"}})
+        );
+        assert_eq!(Box::<str>::json_schema(), rust_type!({"type": "string"}));
+    }
+}
@@ -1,7 +1,18 @@
-#[derive(Clone, Copy, Eq, Debug, Display, PartialEq)]
-pub(in crate) enum KeywordType {
+/// Keyword responsible for a `ValidationError` or a `SchemaError`.
+#[derive(Clone, Copy, Eq, Debug, Display, Hash, PartialEq)]
+pub enum KeywordType {
+    /// The error is not related to a specific keyword (ie. the schema is not an object)
     Unknown,
     Type,
     Properties,
+    PatternProperties,
+    AdditionalProperties,
     Ref,
+    AllOf,
+    Discriminator,
+    ReadOnly,
+    WriteOnly,
+    Vocabulary,
+    /// Keyword registered via `ScopeBuilder::register_custom_keyword`
+    Custom(&'static str),
 }
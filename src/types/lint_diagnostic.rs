@@ -0,0 +1,42 @@
+use crate::types::lint_rule::LintRule;
+use std::fmt::{Display, Error, Formatter};
+use url::Url;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintDiagnostic {
+    rule: LintRule,
+    path: Url,
+    message: String,
+}
+
+impl Display for LintDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{}: {} (path={})", self.rule, self.message, self.path)
+    }
+}
+
+impl LintDiagnostic {
+    #[must_use]
+    pub fn new(rule: LintRule, path: &Url, message: &str) -> Self {
+        Self {
+            rule,
+            path: path.clone(),
+            message: message.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub const fn rule(&self) -> LintRule {
+        self.rule
+    }
+
+    #[must_use]
+    pub const fn path(&self) -> &Url {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Clone, Copy, EnumIter, Eq, Debug, Display, Hash, PartialEq)]
+pub enum LintRule {
+    // `minimum` greater than `maximum` (and similar for lengths, items and properties)
+    UnsatisfiableRange,
+    // `required` property that is not allowed by `additionalProperties: false`
+    RequiredForbiddenProperty,
+    // `oneOf` branch that can never be the only matching one
+    UnreachableOneOfBranch,
+    // `type` of the schema and of its `allOf` schemas have no type in common
+    AllOfTypeContradiction,
+    // Root `definitions` that are not referenced within the document
+    UnusedDefinition,
+    // Property schema without `description`
+    MissingDescription,
+}
@@ -0,0 +1,168 @@
+use crate::{
+    lints::{check_schema, unused_definition, walk_schema},
+    types::{lint_diagnostic::LintDiagnostic, lint_rule::LintRule},
+};
+use json_trait_rs::JsonType;
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+use url::Url;
+
+#[derive(Clone, Debug)]
+pub struct Linter {
+    enabled_rules: HashSet<LintRule>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+impl Linter {
+    /// All the rules are enabled by default.
+    #[must_use]
+    pub fn create() -> Self {
+        Self {
+            enabled_rules: LintRule::iter().collect(),
+        }
+    }
+
+    pub fn enable(&mut self, rule: LintRule) -> &mut Self {
+        let _ = self.enabled_rules.insert(rule);
+        self
+    }
+
+    pub fn disable(&mut self, rule: LintRule) -> &mut Self {
+        let _ = self.enabled_rules.remove(&rule);
+        self
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self, rule: LintRule) -> bool {
+        self.enabled_rules.contains(&rule)
+    }
+
+    /// Lint the schema and all its subschemas, path is used to locate the reported diagnostics.
+    #[must_use]
+    pub fn lint<J: JsonType>(&self, path: &Url, raw_schema: &J) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+        walk_schema(path, raw_schema, &mut |schema_path, schema| {
+            for rule in LintRule::iter().filter(|rule| self.is_enabled(*rule)) {
+                diagnostics.extend(check_schema(rule, schema_path, schema));
+            }
+        });
+        if self.is_enabled(LintRule::UnusedDefinition) {
+            diagnostics.extend(unused_definition::check(path, raw_schema));
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Linter;
+    use crate::{
+        hash_set,
+        types::{
+            draft_version::DraftVersion,
+            lint_rule::LintRule,
+            schema::Schema,
+            scope_builder::{scope_builder_create_and_build, ScopeBuilder},
+        },
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use loader_rs::{loaders::RustTypeLoader, LoaderTrait};
+    use std::{collections::HashSet, sync::Arc};
+    use url::Url;
+
+    fn raw_schema() -> RustType {
+        rust_type!({
+            "definitions": {"unused": {}},
+            "properties": {
+                "age": {"description": "Age", "minimum": 10, "maximum": 1},
+                "name": {"type": "string"},
+            },
+            "required": ["id"],
+            "additionalProperties": false,
+        })
+    }
+
+    fn reported_rules(linter: &Linter) -> HashSet<LintRule> {
+        linter
+            .lint(&Url::parse("memory://schema").unwrap(), &raw_schema())
+            .iter()
+            .map(|diagnostic| diagnostic.rule())
+            .collect()
+    }
+
+    #[test]
+    fn lint_with_all_rules() {
+        assert_eq!(
+            reported_rules(&Linter::create()),
+            hash_set![
+                LintRule::UnsatisfiableRange,
+                LintRule::RequiredForbiddenProperty,
+                LintRule::UnusedDefinition,
+                LintRule::MissingDescription
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_with_disabled_rules() {
+        let mut linter = Linter::create();
+        let _ = linter.disable(LintRule::MissingDescription).disable(LintRule::UnusedDefinition);
+        assert_eq!(reported_rules(&linter), hash_set![LintRule::UnsatisfiableRange, LintRule::RequiredForbiddenProperty]);
+
+        let _ = linter.enable(LintRule::UnusedDefinition);
+        assert_eq!(
+            reported_rules(&linter),
+            hash_set![LintRule::UnsatisfiableRange, LintRule::RequiredForbiddenProperty, LintRule::UnusedDefinition]
+        );
+    }
+
+    #[test]
+    fn lint_compiled_schema() {
+        let diagnostics = scope_builder_create_and_build(DraftVersion::Draft4, raw_schema(), &|scope_builder, generated_url, raw_schema| {
+            let schema = Schema::create(scope_builder, generated_url, raw_schema).expect("Schema is supposed to be valid");
+            scope_builder.lint(&Linter::create(), schema.path()).expect("Schema is supposed to be retrievable")
+        });
+        let mut diagnostic_paths = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.path().fragment().unwrap_or("").to_string())
+            .collect::<Vec<_>>();
+        diagnostic_paths.sort();
+        assert_eq!(diagnostic_paths, vec!["", "/definitions/unused", "/properties/age", "/properties/name"]);
+    }
+
+    #[test]
+    fn lint_referenced_schemas() {
+        let loader = RustTypeLoader::default();
+        loader.save_in_cache(
+            &Url::parse("memory://address").unwrap(),
+            &Arc::new(rust_type!({"properties": {"zip": {"description": "Zip code", "minimum": 10, "maximum": 1}}})),
+        );
+        let mut scope_builder = ScopeBuilder::create(DraftVersion::Draft4, loader);
+        let path = scope_builder.inject_schema(&Arc::new(rust_type!({
+            "properties": {
+                "address": {"$ref": "memory://address"},
+                "child": {"$ref": "#"},
+                "age": {"description": "Age", "minimum": 10, "maximum": 1},
+            },
+        })));
+
+        let diagnostics = scope_builder.lint(&Linter::create(), &path).expect("Schemas are supposed to be retrievable");
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.rule(), diagnostic.path().to_string()))
+                .collect::<HashSet<_>>(),
+            hash_set![
+                (LintRule::UnsatisfiableRange, format!("{}#/properties/age", path)),
+                (LintRule::UnsatisfiableRange, "memory://address#/properties/zip".to_string())
+            ]
+        );
+        // The recursive reference to the root schema does not lint it again
+        assert_eq!(diagnostics.len(), 2);
+    }
+}
@@ -1,9 +1,21 @@
+pub(in crate) mod annotation;
+pub(in crate) mod custom_keyword;
 pub(in crate) mod draft_version;
+pub(in crate) mod instance;
+pub(in crate) mod json_schema;
 pub(in crate) mod keyword_type;
+pub(in crate) mod lint_diagnostic;
+pub(in crate) mod lint_rule;
+pub(in crate) mod linter;
+pub(in crate) mod openapi_context;
 pub(in crate) mod schema;
 pub(in crate) mod schema_error;
+pub(in crate) mod schema_warning;
 pub(in crate) mod scope;
 pub(in crate) mod scope_builder;
+pub(in crate) mod strict_mode;
+pub(in crate) mod type_generator;
 pub(in crate) mod validation_error;
 pub(in crate) mod validator;
 pub(in crate) mod validator_error_iterator;
+pub(in crate) mod vocabulary;
@@ -0,0 +1,7 @@
+// OpenAPI 3.0 readOnly and writeOnly keywords are assertions only once it is known
+// whether the validated instance is part of a request or of a response
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+pub enum OpenApiContext {
+    Request,
+    Response,
+}
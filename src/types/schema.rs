@@ -1,29 +1,37 @@
 use crate::{
     keywords::{compile_draft_validators, DraftValidator},
     types::{
-        draft_version::DraftVersion, keyword_type::KeywordType, schema_error::SchemaError, scope_builder::ScopeBuilder, validation_error::ValidationError,
+        annotation::{schema_annotations, Annotation},
+        draft_version::DraftVersion,
+        keyword_type::KeywordType,
+        schema_error::SchemaError,
+        scope_builder::ScopeBuilder,
+        validation_error::ValidationError,
         validator_error_iterator::ValidationErrorIterator,
     },
 };
 use json_trait_rs::{JsonType, PrimitiveType, RustType};
-use std::{ops::Deref, sync::Arc};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use url::Url;
 
+/// Compiled schema, created via `ScopeBuilder::schema` and usable for validation after `ScopeBuilder::build`.
 #[derive(Debug)]
-pub(in crate) struct Schema {
+pub struct Schema {
     pub(in crate) draft_version: DraftVersion,
     pub(in crate) validators: Vec<DraftValidator>,
     pub(in crate) path: Url,
-    pub(in crate) raw_schema: Arc<RustType>,
-    is_initialised: bool,
-}
-
-impl Deref for Schema {
-    type Target = RustType;
-
-    fn deref(&self) -> &Self::Target {
-        &*self.raw_schema
-    }
+    // Annotations are not needed for validation, they are retained only for annotations and validate_with_defaults
+    pub(in crate) annotations: Vec<(String, RustType)>,
+    // Atomic as schemas are initialised via shared references (other schemas could already be referencing them)
+    is_initialised: AtomicBool,
 }
 
 impl Schema {
@@ -32,17 +40,16 @@ impl Schema {
         T: 'static + JsonType,
         J: JsonType,
     {
-        let raw_schema_rust_type = raw_schema.to_rust_type();
         if raw_schema.is_object() {
-            let mut schema = Self {
+            // Validators are compiled directly against the caller's representation,
+            // so no copy of the raw schema is retained once compilation is over
+            Ok(Self {
                 draft_version: scope_builder.draft_version,
                 path: path.clone(),
-                validators: Vec::with_capacity(0),
-                raw_schema: Arc::new(raw_schema_rust_type),
-                is_initialised: false,
-            };
-            schema.validators = compile_draft_validators::<T>(scope_builder, &mut schema)?;
-            Ok(schema)
+                annotations: schema_annotations(raw_schema, &scope_builder.custom_keywords()),
+                validators: compile_draft_validators(scope_builder, path, raw_schema)?,
+                is_initialised: AtomicBool::new(false),
+            })
         } else {
             Err(SchemaError::Malformed {
                 path: path.clone(),
@@ -51,21 +58,31 @@ impl Schema {
                     "raw_schema type is {} while is expected type {}. raw_schema: {}",
                     raw_schema.primitive_type(),
                     PrimitiveType::Object,
-                    raw_schema_rust_type
+                    raw_schema.to_rust_type()
                 ),
             })
         }
     }
 
-    pub(in crate) fn initialise(&mut self) {
-        if !self.is_initialised {
-            self.is_initialised = true;
-            // TODO: initialise validators if needed
+    pub(in crate) fn initialise(&self, schema_cache: &HashMap<Url, Arc<Schema>>) {
+        if !self.is_initialised.load(Ordering::SeqCst) {
+            // References are resolved before flagging the schema as initialised, so validation never sees them unresolved
+            self.validators.iter().for_each(|validator| validator.initialise(schema_cache));
+            self.is_initialised.store(true, Ordering::SeqCst);
         }
     }
 
-    pub(in crate) fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
-        if self.is_initialised {
+    /// Location of the schema, used to resolve the relative `$ref`s.
+    #[must_use]
+    pub const fn path(&self) -> &Url {
+        &self.path
+    }
+
+    /// Validation errors of `value`, located relatively to `path` (usually `#`).
+    ///
+    /// Schemas used before `ScopeBuilder::build` report a single `Uninitialised schema` error.
+    pub fn validation_errors<T: 'static + JsonType>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if self.is_initialised.load(Ordering::SeqCst) {
             // TODO: Find a way to avoid the collection of the validation errors (after all we are returning an iterator)
             ValidationErrorIterator::new(self.validators.iter().flat_map(|validator| validator.validation_errors(path, value)).collect::<Vec<_>>())
         } else {
@@ -73,8 +90,114 @@ impl Schema {
         }
     }
 
-    pub(in crate) fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
-        self.validators.iter().all(|validator| validator.is_valid(path, value))
+    /// Same as `validation_errors(path, value).next().is_none()`, but it stops at the first error.
+    ///
+    /// Schemas used before `ScopeBuilder::build` are never valid, as they report the `Uninitialised schema` error.
+    #[must_use]
+    pub fn is_valid<T: 'static + JsonType>(&self, path: &str, value: &T) -> bool {
+        self.is_initialised.load(Ordering::SeqCst) && self.validators.iter().all(|validator| validator.is_valid(path, value))
+    }
+
+    /// Same as `validation_errors`, but objects with many properties are validated across the rayon thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        if self.is_initialised.load(Ordering::SeqCst) {
+            ValidationErrorIterator::new(
+                self.validators
+                    .iter()
+                    .flat_map(|validator| validator.par_validation_errors(path, value))
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Unknown, "Uninitialised schema"))
+        }
+    }
+
+    /// Validate instances in parallel, the validation errors are reported in the order of the instances.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn validate_batch<T: 'static + JsonType + Sync>(&self, path: &str, instances: &[T]) -> Vec<Vec<ValidationError>> {
+        instances.par_iter().map(|instance| self.par_validation_errors(path, instance).collect()).collect()
+    }
+
+    /// Validate a copy of value, patched with the `default`s of the missing properties.
+    /// Defaults are looked up through `properties`, `$ref` and `allOf`.
+    #[must_use]
+    pub fn validate_with_defaults<T: 'static + JsonType>(&self, path: &str, value: &T) -> (RustType, ValidationErrorIterator) {
+        let mut patched_value = value.to_rust_type();
+        self.apply_defaults(&mut patched_value);
+        let validation_errors = self.validation_errors(path, &patched_value);
+        (patched_value, validation_errors)
+    }
+
+    /// Validate a copy of value where scalars are converted, when lossless, into the types declared by `type`.
+    /// This allows validation of query and form parameters, as they are always received as strings.
+    #[must_use]
+    pub fn validate_with_coercion<T: 'static + JsonType>(&self, path: &str, value: &T) -> (RustType, ValidationErrorIterator) {
+        let mut coerced_value = value.to_rust_type();
+        self.coerce(&mut coerced_value);
+        let validation_errors = self.validation_errors(path, &coerced_value);
+        (coerced_value, validation_errors)
+    }
+
+    pub(in crate) fn coerce(&self, value: &mut RustType) {
+        self.validators.iter().for_each(|validator| validator.coerce(value));
+    }
+
+    /// Copy of value without the properties that are not allowed by `properties`, `patternProperties` and `additionalProperties: false`.
+    #[must_use]
+    pub fn remove_additional_properties<T: JsonType>(&self, value: &T) -> RustType {
+        let mut sanitised_value = value.to_rust_type();
+        self.sanitise(&mut sanitised_value);
+        sanitised_value
+    }
+
+    pub(in crate) fn sanitise(&self, value: &mut RustType) {
+        self.validators.iter().for_each(|validator| validator.sanitise(value));
+    }
+
+    pub(in crate) fn apply_defaults(&self, value: &mut RustType) {
+        self.validators.iter().for_each(|validator| validator.apply_defaults(value));
+    }
+
+    pub(in crate) fn annotation(&self, keyword: &str) -> Option<&RustType> {
+        self.annotations
+            .iter()
+            .find_map(|(annotation_keyword, value)| if annotation_keyword == keyword { Some(value) } else { None })
+    }
+
+    /// Annotations of the subschemas successfully evaluated against value, keyed by instance and schema location.
+    #[must_use]
+    pub fn annotations<T: 'static + JsonType>(&self, path: &str, value: &T) -> Vec<Annotation> {
+        let mut annotations = Vec::new();
+        let _ = self.collect_annotations(path, value, &mut annotations);
+        annotations
+    }
+
+    // Returns whether value is valid against the schema
+    pub(in crate) fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, annotations: &mut Vec<Annotation>) -> bool {
+        let annotations_count = annotations.len();
+        annotations.extend(
+            self.annotations
+                .iter()
+                .map(|(keyword, annotation_value)| Annotation::new(path, &self.path, keyword, annotation_value)),
+        );
+        let is_valid = self.validators.iter().all(|validator| validator.collect_annotations(path, value, annotations));
+        if !is_valid {
+            // Annotations of failing schemas (and of their subschemas) are dropped
+            annotations.truncate(annotations_count);
+        }
+        is_valid
+    }
+
+    // `default` of a schema that is only a `$ref` is the one of the referenced schema
+    pub(in crate) fn default_value(&self) -> Option<RustType> {
+        self.annotation("default").cloned().or_else(|| {
+            self.validators.iter().find_map(|validator| match validator {
+                DraftValidator::Ref(ref_validator) => ref_validator.referenced_schema().and_then(|referenced_schema| referenced_schema.default_value()),
+                _ => None,
+            })
+        })
     }
 
     #[cfg(test)]
@@ -95,11 +218,42 @@ pub(in crate) fn draft4_schema(raw_schema: RustType) -> Result<Schema, SchemaErr
     })
 }
 
+// Compiled via ScopeBuilder::schema (as users do), so the root schema is cached and initialised by ScopeBuilder::build.
+// It has to be used by tests validating against the root schema, draft4_schema is meant to inspect the compiled validators.
+#[cfg(test)]
+pub(in crate) fn initialised_draft4_schema(raw_schema: RustType) -> Result<Arc<Schema>, SchemaError> {
+    crate::types::scope_builder::scope_builder_create_and_build(DraftVersion::Draft4, raw_schema, &|scope_builder, generated_url, raw_schema| {
+        scope_builder.schema(generated_url, raw_schema)
+    })
+}
+
+#[cfg(test)]
+pub(in crate) fn openapi30_schema(raw_schema: RustType, openapi_context: Option<crate::types::openapi_context::OpenApiContext>) -> Result<Schema, SchemaError> {
+    crate::types::scope_builder::scope_builder_create_and_build(DraftVersion::OpenApi30, raw_schema, &|scope_builder, generated_url, raw_schema| {
+        if let Some(openapi_context) = openapi_context {
+            let _ = scope_builder.use_openapi_context(openapi_context);
+        }
+        Schema::create(scope_builder, generated_url, raw_schema)
+    })
+}
+
+// Same as initialised_draft4_schema, for tests validating against the root schema
+#[cfg(test)]
+pub(in crate) fn initialised_openapi30_schema(raw_schema: RustType, openapi_context: Option<crate::types::openapi_context::OpenApiContext>) -> Result<Arc<Schema>, SchemaError> {
+    crate::types::scope_builder::scope_builder_create_and_build(DraftVersion::OpenApi30, raw_schema, &|scope_builder, generated_url, raw_schema| {
+        if let Some(openapi_context) = openapi_context {
+            let _ = scope_builder.use_openapi_context(openapi_context);
+        }
+        scope_builder.schema(generated_url, raw_schema)
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{draft4_schema, Schema};
+    use super::{draft4_schema, initialised_draft4_schema, Schema};
     use crate::types::{draft_version::DraftVersion, keyword_type::KeywordType, schema_error::SchemaError, scope_builder::scope_builder_create, validation_error::ValidationError};
     use json_trait_rs::{rust_type, PrimitiveType, RustType};
+    use std::{sync::Arc, thread};
     use test_case::test_case;
 
     #[test_case(&rust_type!(null), PrimitiveType::Null)]
@@ -129,4 +283,234 @@ mod tests {
             vec![ValidationError::new("#/bool", KeywordType::Unknown, "Uninitialised schema",)],
         );
     }
+
+    #[test]
+    fn unbuilt_schema_is_never_valid() {
+        let unbuilt_schema = scope_builder_create(DraftVersion::Draft4, rust_type!({}), &|scope_builder, generated_url, raw_schema| {
+            scope_builder.schema(generated_url, raw_schema)
+        })
+        .1
+        .expect("Schema is supposed to be valid");
+        assert!(!unbuilt_schema.is_valid("#", &rust_type!({"bool": true})));
+        assert_eq!(unbuilt_schema.validation_errors("#", &rust_type!({"bool": true})).count(), 1);
+    }
+
+    #[test]
+    fn validate_schema_at_pointer() {
+        let schema = scope_builder_create(
+            DraftVersion::OpenApi30,
+            rust_type!({"openapi": "3.0.3", "components": {"schemas": {"Pet": {"properties": {"name": {"type": "string", "nullable": true}}}}}}),
+            &|scope_builder, generated_url, _| {
+                let schema = scope_builder.schema_at_pointer(generated_url, "/components/schemas/Pet");
+                let _ = scope_builder.build();
+                schema
+            },
+        )
+        .1
+        .expect("Schema is supposed to be valid");
+        assert!(schema.is_valid("#", &rust_type!({"name": null})));
+        assert_eq!(
+            schema.validation_errors("#", &rust_type!({"name": 1})).collect::<Vec<_>>(),
+            vec![ValidationError::new("#/name", KeywordType::Type, "Invalid Type")],
+        );
+    }
+
+    #[test]
+    fn dangling_references() {
+        let dangling_references = scope_builder_create(
+            DraftVersion::Draft4,
+            rust_type!({
+                "definitions": {"name": {"type": "string"}},
+                "properties": {
+                    "name": {"$ref": "#/definitions/name"},
+                    "age": {"$ref": "#/definitions/age"},
+                    "address": {"default": {"$ref": "#/definitions/address"}},
+                },
+            }),
+            &|scope_builder, generated_url, raw_schema| {
+                scope_builder
+                    .dangling_references(generated_url, raw_schema)
+                    .into_iter()
+                    .map(|(path, json_reference, _)| (path.fragment().unwrap_or("").to_string(), json_reference))
+                    .collect::<Vec<_>>()
+            },
+        )
+        .1;
+        assert_eq!(dangling_references, vec![("/properties/age".to_string(), "#/definitions/age".to_string())]);
+    }
+
+    fn schema_with_defaults() -> Arc<Schema> {
+        initialised_draft4_schema(rust_type!({
+            "definitions": {"country": {"type": "string", "default": "IE"}},
+            "properties": {
+                "name": {"type": "string", "default": "anonymous"},
+                "address": {"default": {}, "properties": {"country": {"$ref": "#/definitions/country"}}},
+            },
+            "allOf": [{"properties": {"active": {"default": true}}}],
+        }))
+        .expect("Schema is supposed to be valid")
+    }
+
+    #[test_case(&rust_type!({}) => (rust_type!({"name": "anonymous", "address": {"country": "IE"}, "active": true}), vec![]))]
+    #[test_case(&rust_type!({"address": {}, "active": false}) => (rust_type!({"name": "anonymous", "address": {"country": "IE"}, "active": false}), vec![]))]
+    #[test_case(
+        &rust_type!({"name": 1, "address": {"country": "UK"}})
+        => (rust_type!({"name": 1, "address": {"country": "UK"}, "active": true}), vec![ValidationError::new("#/name", KeywordType::Type, "Invalid Type")])
+    )]
+    #[test_case(&rust_type!("not an object") => (rust_type!("not an object"), vec![]))]
+    fn validate_with_defaults(value: &RustType) -> (RustType, Vec<ValidationError>) {
+        let (patched_value, validation_errors) = schema_with_defaults().validate_with_defaults("#", value);
+        (patched_value, validation_errors.collect())
+    }
+
+    #[test_case(&rust_type!({"page": "2", "filter": {"active": "true"}}) => (rust_type!({"page": 2, "filter": {"active": true}}), vec![]))]
+    #[test_case(
+        &rust_type!({"page": "second", "filter": {"active": "yes"}})
+        => (rust_type!({"page": "second", "filter": {"active": "yes"}}), vec![
+            ValidationError::new("#/filter/active", KeywordType::Type, "Invalid Type"),
+            ValidationError::new("#/page", KeywordType::Type, "Invalid Type"),
+        ])
+    )]
+    fn validate_with_coercion(value: &RustType) -> (RustType, Vec<ValidationError>) {
+        let schema = initialised_draft4_schema(rust_type!({
+            "definitions": {"flag": {"type": "boolean"}},
+            "properties": {"page": {"type": "integer"}, "filter": {"allOf": [{"properties": {"active": {"$ref": "#/definitions/flag"}}}]}},
+        }))
+        .expect("Schema is supposed to be valid");
+        let (coerced_value, validation_errors) = schema.validate_with_coercion("#", value);
+        let mut validation_errors = validation_errors.collect::<Vec<_>>();
+        // properties are validated in arbitrary order
+        validation_errors.sort_by_key(ToString::to_string);
+        (coerced_value, validation_errors)
+    }
+
+    #[test_case(&rust_type!({"name": "n", "extra": 1}) => rust_type!({"name": "n"}))]
+    #[test_case(
+        &rust_type!({"name": "n", "address": {"city": "c", "zip": "z"}, "tags": {"x-a": {"label": "l", "color": "c"}}})
+        => rust_type!({"name": "n", "address": {"city": "c"}, "tags": {"x-a": {"label": "l"}}})
+    )]
+    #[test_case(&rust_type!(["not", "an", "object"]) => rust_type!(["not", "an", "object"]))]
+    fn remove_additional_properties(value: &RustType) -> RustType {
+        let schema = draft4_schema(rust_type!({
+            "definitions": {"address": {"properties": {"city": {}}, "additionalProperties": false}},
+            "properties": {
+                "name": {},
+                "address": {"$ref": "#/definitions/address"},
+                "tags": {"patternProperties": {"^x-": {"allOf": [{"properties": {"label": {}}, "additionalProperties": false}]}}},
+            },
+            "additionalProperties": false,
+        }))
+        .expect("Schema is supposed to be valid");
+        schema.remove_additional_properties(value)
+    }
+
+    fn annotated_schema() -> Arc<Schema> {
+        initialised_draft4_schema(rust_type!({
+            "title": "User",
+            "definitions": {"email": {"type": "string", "description": "Contact email", "x-widget": "email"}},
+            "properties": {
+                "email": {"$ref": "#/definitions/email"},
+                "nickname": {"type": "string", "deprecated": true},
+            },
+        }))
+        .expect("Schema is supposed to be valid")
+    }
+
+    #[test_case(&rust_type!({"email": "e@mail"}) => vec![
+        ("#".to_string(), "".to_string(), "title".to_string(), rust_type!("User")),
+        ("#/email".to_string(), "/definitions/email".to_string(), "description".to_string(), rust_type!("Contact email")),
+        ("#/email".to_string(), "/definitions/email".to_string(), "x-widget".to_string(), rust_type!("email")),
+    ])]
+    #[test_case(&rust_type!({"nickname": "n"}) => vec![
+        ("#".to_string(), "".to_string(), "title".to_string(), rust_type!("User")),
+        ("#/nickname".to_string(), "/properties/nickname".to_string(), "deprecated".to_string(), rust_type!(true)),
+    ])]
+    #[test_case(&rust_type!({"email": 1}) => vec![])]
+    #[test_case(&rust_type!({"email": 1, "nickname": "n"}) => vec![])]
+    fn annotations(value: &RustType) -> Vec<(String, String, String, RustType)> {
+        let mut annotations = annotated_schema()
+            .annotations("#", value)
+            .iter()
+            .map(|annotation| {
+                (
+                    annotation.instance_location().to_string(),
+                    annotation.schema_location().fragment().unwrap_or("").to_string(),
+                    annotation.keyword().to_string(),
+                    annotation.value().clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        annotations.sort_by(|left, right| (&left.0, &left.2).cmp(&(&right.0, &right.2)));
+        annotations
+    }
+
+    #[test]
+    fn validate_concurrently() {
+        let schema = initialised_draft4_schema(rust_type!({"properties": {"bool": {"type": "boolean"}}})).expect("Schema is supposed to be valid");
+        let handles = (0..16_usize)
+            .map(|thread_index| {
+                let schema = schema.clone();
+                thread::spawn(move || {
+                    (0..100_usize).all(|iteration| {
+                        let expected_errors = (thread_index + iteration) % 2;
+                        let object = if expected_errors == 0 { rust_type!({"bool": true}) } else { rust_type!({"bool": "text"}) };
+                        schema.is_valid("#", &object) == (expected_errors == 0) && schema.validation_errors("#", &object).count() == expected_errors
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        assert!(handles.into_iter().all(|handle| handle.join().expect("Validation thread is not expected to panic")));
+    }
+
+    #[cfg(feature = "rayon")]
+    fn large_object(properties: usize) -> RustType {
+        // Every third property is not an integer
+        RustType::Object(
+            (0..properties)
+                .map(|index| (format!("p{}", index), if index % 3 == 0 { rust_type!("text") } else { RustType::Integer(index as i128) }))
+                .collect(),
+        )
+    }
+
+    #[cfg(feature = "rayon")]
+    fn sorted(mut validation_errors: Vec<ValidationError>) -> Vec<ValidationError> {
+        // properties are validated in arbitrary order
+        validation_errors.sort_by_key(ToString::to_string);
+        validation_errors
+    }
+
+    #[cfg(feature = "rayon")]
+    fn type_errors(paths: &[&str]) -> Vec<ValidationError> {
+        sorted(paths.iter().map(|path| ValidationError::new(path, KeywordType::Type, "Invalid Type")).collect())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn validate_batch() {
+        let schema =
+            initialised_draft4_schema(rust_type!({"properties": {"p0": {"type": "string"}}, "additionalProperties": {"type": "integer"}})).expect("Schema is supposed to be valid");
+        let instances = vec![rust_type!({"p0": "text", "p1": 1}), rust_type!({"p0": 0}), large_object(10), rust_type!(null)];
+        assert_eq!(
+            schema.validate_batch("#", &instances).into_iter().map(sorted).collect::<Vec<_>>(),
+            vec![vec![], type_errors(&["#/p0"]), type_errors(&["#/p3", "#/p6", "#/p9"]), vec![]],
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_validation_errors_of_large_objects() {
+        let schema = initialised_draft4_schema(rust_type!({
+            "definitions": {"integer": {"type": "integer"}},
+            "allOf": [{"patternProperties": {"^p": {"$ref": "#/definitions/integer"}}}],
+            "additionalProperties": {"type": "integer"},
+        }))
+        .expect("Schema is supposed to be valid");
+        let instance = large_object(1000);
+        // Patterns of allOf are not siblings of additionalProperties, so both report the properties that are not integers
+        let expected_paths = (0..1000).step_by(3).flat_map(|index| vec![format!("#/p{}", index); 2]).collect::<Vec<_>>();
+        assert_eq!(
+            sorted(schema.par_validation_errors("#", &instance).collect()),
+            type_errors(&expected_paths.iter().map(String::as_str).collect::<Vec<_>>()),
+        );
+    }
 }
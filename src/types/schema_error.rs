@@ -1,20 +1,30 @@
-use crate::types::{keyword_type::KeywordType, validation_error::ValidationError};
+use crate::types::{keyword_type::KeywordType, schema_warning::SchemaWarning, validation_error::ValidationError};
 use failure::Fail;
 use loader_rs::LoaderError;
 use url::{ParseError, Url};
 
+/// Reason for a schema not being compiled.
 #[derive(Debug, Fail)]
-pub(in crate) enum SchemaError {
+pub enum SchemaError {
     #[fail(display = "Unknown error")]
     Unknown,
+    /// The value of `keyword`, in the schema located by `path`, is not valid
     #[fail(display = "Malformed Schema: path={}, detail={}", path, detail)]
     Malformed { path: Url, keyword: KeywordType, detail: String },
+    /// A reference is not a valid `Url`
     #[fail(display = "Url Parsing error: {}", 0)]
     UrlParse(ParseError),
     #[fail(display = "Validation error: {}", 0)]
     Validation(ValidationError),
+    /// A schema (or a referenced schema) could not be retrieved
     #[fail(display = "Loader Error: {}", 0)]
     LoaderError(LoaderError),
+    /// The schema document could not be parsed
+    #[fail(display = "Parsing error: {}", 0)]
+    Parse(String),
+    /// The schema contains keywords that are not validated (`StrictMode::Strict` only)
+    #[fail(display = "Strict mode: {}", 0)]
+    Strict(SchemaWarning),
 }
 
 impl Default for SchemaError {
@@ -0,0 +1,28 @@
+use crate::types::draft_version::DraftVersion;
+use std::fmt::{Display, Error, Formatter};
+use url::Url;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaWarning {
+    // Keyword not defined by any supported draft nor registered as custom keyword
+    UnknownKeyword { path: Url, keyword: String },
+    // Keyword defined by a different draft than the selected one
+    UnsupportedKeyword { path: Url, keyword: String, draft_version: DraftVersion },
+    // Keyword that has no effect as sibling of `$ref`
+    IgnoredRefSibling { path: Url, keyword: String },
+    // Keyword defined by the selected draft that is not validated (not implemented yet, or excluded by the dialect)
+    IgnoredKeyword { path: Url, keyword: String },
+}
+
+impl Display for SchemaWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Self::UnknownKeyword { path, keyword } => write!(f, "Unknown keyword `{}`: path={}", keyword, path),
+            Self::UnsupportedKeyword { path, keyword, draft_version } => {
+                write!(f, "Keyword `{}` is not supported by {}: path={}", keyword, draft_version, path)
+            }
+            Self::IgnoredRefSibling { path, keyword } => write!(f, "Keyword `{}` is ignored as sibling of `$ref`: path={}", keyword, path),
+            Self::IgnoredKeyword { path, keyword } => write!(f, "Keyword `{}` is not validated: path={}", keyword, path),
+        }
+    }
+}
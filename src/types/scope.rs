@@ -1,9 +1,32 @@
-use crate::types::{draft_version::DraftVersion, schema::Schema};
+use crate::types::{draft_version::DraftVersion, schema::Schema, scope_builder::schema_cache_key};
 use std::{collections::HashMap, sync::Arc};
 use url::Url;
 
-pub(in crate) struct Scope {
+/// Schemas compiled by a `ScopeBuilder`, ready for validation.
+pub struct Scope {
     pub(in crate) draft_version: DraftVersion,
-    // TODO: Verify if we need a thread-safe cache or this is good enough
+    // The cache is not modified after ScopeBuilder::build, so it can be safely shared across threads
     pub(in crate) schema_cache: HashMap<Url, Arc<Schema>>,
 }
+
+impl Scope {
+    /// Compiled schema located by `path`, if it was compiled before `ScopeBuilder::build`.
+    #[must_use]
+    pub fn schema(&self, path: &Url) -> Option<Arc<Schema>> {
+        self.schema_cache.get(&schema_cache_key(path)).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scope;
+    use crate::types::schema::Schema;
+
+    fn assert_send_and_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn compiled_types_are_send_and_sync() {
+        assert_send_and_sync::<Scope>();
+        assert_send_and_sync::<Schema>();
+    }
+}
@@ -1,17 +1,61 @@
-use crate::types::{draft_version::DraftVersion, schema::Schema, schema_error::SchemaError, scope::Scope};
+use crate::{
+    keywords::{draft_keyword_name, draft_keywords, ref_::full_uri},
+    lints::{is_within, references, walk_schema},
+    types::{
+        custom_keyword::{CustomKeyword, CustomKeywordRegistration},
+        draft_version::DraftVersion,
+        keyword_type::KeywordType,
+        lint_diagnostic::LintDiagnostic,
+        linter::Linter,
+        openapi_context::OpenApiContext,
+        schema::Schema,
+        schema_error::SchemaError,
+        schema_warning::SchemaWarning,
+        scope::Scope,
+        strict_mode::{keyword_warnings, StrictMode},
+        vocabulary::{Dialect, Vocabulary},
+    },
+};
 use json_trait_rs::JsonType;
 #[cfg(test)]
 use json_trait_rs::RustType;
-use loader_rs::{LoaderError, LoaderTrait};
-use std::{collections::HashMap, sync::Arc};
+use loader_rs::LoaderTrait;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use url::Url;
 use uuid::Uuid;
 
+/// Compiles schemas (and the schemas they reference) of a given `DraftVersion`, the raw schemas are retrieved via the loader.
+///
+/// Compiled schemas are cached by `Url`, and become usable for validation once `build` is called.
 #[derive(Debug)]
-pub(in crate) struct ScopeBuilder<T: JsonType> {
+pub struct ScopeBuilder<T: JsonType> {
     pub(in crate) draft_version: DraftVersion,
     loader: Box<dyn LoaderTrait<T>>,
     schema_cache: HashMap<Url, Arc<Schema>>,
+    // Schemas whose compilation is in progress, references to them are resolved once they are cached
+    compiling_schemas: HashSet<Url>,
+    custom_keywords: Vec<CustomKeywordRegistration>,
+    vocabularies: HashMap<Url, Vocabulary>,
+    dialect: Option<Dialect>,
+    pub(in crate) openapi_context: Option<OpenApiContext>,
+    strict_mode: StrictMode,
+    warnings: Vec<SchemaWarning>,
+    // Verification of the raw schemas retrieved via the loader (ie. YAML values without JSON equivalent)
+    raw_schema_check: Option<fn(&Url, &T) -> Result<(), SchemaError>>,
+}
+
+// `memory://schema#` and `memory://schema` address the same schema (ie. `$ref: "#"` refers to the root schema)
+pub(in crate) fn schema_cache_key(path: &Url) -> Url {
+    if path.fragment() == Some("") {
+        let mut key = path.clone();
+        key.set_fragment(None);
+        key
+    } else {
+        path.clone()
+    }
 }
 
 fn generate_random_url() -> Url {
@@ -19,7 +63,8 @@ fn generate_random_url() -> Url {
 }
 
 impl<T: JsonType> ScopeBuilder<T> {
-    pub(in crate) fn create<L>(draft_version: DraftVersion, loader: L) -> Self
+    #[must_use]
+    pub fn create<L>(draft_version: DraftVersion, loader: L) -> Self
     where
         L: 'static + LoaderTrait<T>,
     {
@@ -27,37 +72,229 @@ impl<T: JsonType> ScopeBuilder<T> {
             draft_version,
             loader: Box::new(loader),
             schema_cache: HashMap::new(),
+            compiling_schemas: HashSet::new(),
+            custom_keywords: Vec::new(),
+            vocabularies: HashMap::new(),
+            dialect: None,
+            openapi_context: None,
+            strict_mode: StrictMode::default(),
+            warnings: Vec::new(),
+            raw_schema_check: None,
         }
     }
 
-    pub(in crate) fn retrieve_schema(&mut self, path: &Url) -> Result<Arc<T>, LoaderError> {
-        self.loader.get_or_fetch_with_result(path)
+    /// Custom keywords are compiled after the draft keywords of every schema compiled from now on.
+    pub fn register_custom_keyword<K: 'static + CustomKeyword>(&mut self, keyword: &'static str) -> &mut Self {
+        self.custom_keywords.push(CustomKeywordRegistration::new::<K>(keyword, None));
+        self
+    }
+
+    /// Same as `register_custom_keyword`, but the keyword is compiled only if the selected draft is `draft_version`.
+    pub fn register_draft_custom_keyword<K: 'static + CustomKeyword>(&mut self, draft_version: DraftVersion, keyword: &'static str) -> &mut Self {
+        self.custom_keywords.push(CustomKeywordRegistration::new::<K>(keyword, Some(draft_version)));
+        self
+    }
+
+    pub(in crate) fn custom_keywords(&self) -> Vec<CustomKeywordRegistration> {
+        self.custom_keywords
+            .iter()
+            .filter(|registration| registration.is_enabled_for(self.draft_version))
+            .chain(self.dialect.iter().flat_map(|dialect| dialect.custom_keywords.iter()))
+            .copied()
+            .collect()
+    }
+
+    /// Vocabularies are looked up by `use_dialect`, so they have to be registered first.
+    pub fn register_vocabulary(&mut self, vocabulary: Vocabulary) -> &mut Self {
+        let _ = self.vocabularies.insert(vocabulary.uri.clone(), vocabulary);
+        self
+    }
+
+    /// Restrict the keywords compiled from now on to the vocabularies declared by the meta-schema.
+    /// The meta-schema is retrieved via the loader, so it could be injected or fetched remotely.
+    ///
+    /// # Errors
+    ///
+    /// The meta-schema cannot be retrieved, or it requires a vocabulary that is not registered.
+    pub fn use_dialect(&mut self, meta_schema_path: &Url) -> Result<&mut Self, SchemaError> {
+        let raw_meta_schema: Arc<T> = self.retrieve_schema(meta_schema_path)?;
+        self.dialect = Some(Dialect::create(meta_schema_path, &*raw_meta_schema, &self.vocabularies)?);
+        Ok(self)
+    }
+
+    /// `readOnly` and `writeOnly` are annotations only, unless the context of the validated instances is known.
+    pub fn use_openapi_context(&mut self, openapi_context: OpenApiContext) -> &mut Self {
+        self.openapi_context = Some(openapi_context);
+        self
+    }
+
+    /// Select how the keywords that are not validated are reported by the schemas compiled from now on.
+    pub fn use_strict_mode(&mut self, strict_mode: StrictMode) -> &mut Self {
+        self.strict_mode = strict_mode;
+        self
+    }
+
+    /// Keywords not validated by the schemas compiled so far (only collected in `StrictMode::Lenient`).
+    #[must_use]
+    pub fn warnings(&self) -> &[SchemaWarning] {
+        &self.warnings
+    }
+
+    pub(in crate) fn check_keywords<J: JsonType>(&mut self, path: &Url, raw_schema: &J) -> Result<(), SchemaError> {
+        if self.strict_mode == StrictMode::Disabled {
+            return Ok(());
+        }
+
+        let validated_keywords = draft_keywords(self.draft_version)
+            .iter()
+            .filter(|keyword_type| self.is_keyword_enabled(**keyword_type))
+            .filter_map(|keyword_type| draft_keyword_name(*keyword_type))
+            .collect::<Vec<_>>();
+        let custom_keywords = self.custom_keywords().iter().map(|registration| registration.keyword).collect::<Vec<_>>();
+        let mut warnings = keyword_warnings(self.draft_version, &validated_keywords, &custom_keywords, path, raw_schema);
+        if self.strict_mode == StrictMode::Strict && !warnings.is_empty() {
+            Err(SchemaError::Strict(warnings.swap_remove(0)))
+        } else {
+            self.warnings.append(&mut warnings);
+            Ok(())
+        }
+    }
+
+    pub(in crate) fn is_keyword_enabled(&self, keyword_type: KeywordType) -> bool {
+        self.dialect.as_ref().map_or(true, |dialect| dialect.keywords.contains(&keyword_type))
+    }
+
+    pub(in crate) fn use_raw_schema_check(&mut self, raw_schema_check: fn(&Url, &T) -> Result<(), SchemaError>) -> &mut Self {
+        self.raw_schema_check = Some(raw_schema_check);
+        self
     }
 
-    pub(in crate) fn inject_schema(&mut self, raw_schema: &Arc<T>) -> Url {
+    pub(in crate) fn retrieve_schema(&mut self, path: &Url) -> Result<Arc<T>, SchemaError> {
+        let raw_schema = self.loader.get_or_fetch_with_result(path)?;
+        if let Some(raw_schema_check) = self.raw_schema_check {
+            raw_schema_check(path, &*raw_schema)?;
+        }
+        Ok(raw_schema)
+    }
+
+    /// Make the raw schema available to the loader, the returned `Url` identifies it (ie. to call `schema`).
+    pub fn inject_schema(&mut self, raw_schema: &Arc<T>) -> Url {
         let generated_url = generate_random_url();
         self.loader.save_in_cache(&generated_url, raw_schema);
         generated_url
     }
 
-    pub(in crate) fn schema<J: JsonType>(&mut self, path: &Url, raw_schema: &J) -> Result<Arc<Schema>, SchemaError>
+    /// Compile the raw schema located by `path`, and all the schemas it references.
+    ///
+    /// # Errors
+    ///
+    /// The schema, or one of the schemas it references, is malformed or cannot be retrieved.
+    pub fn schema<J: JsonType>(&mut self, path: &Url, raw_schema: &J) -> Result<Arc<Schema>, SchemaError>
     where
         T: 'static,
     {
-        if let Some(cached_schema) = self.schema_cache.get(path) {
+        let key = schema_cache_key(path);
+        if let Some(cached_schema) = self.schema_cache.get(&key) {
             Ok(cached_schema.clone())
         } else {
-            let arc_schema: Arc<Schema> = Arc::new(Schema::create(self, path, raw_schema)?);
-            let _ = self.schema_cache.insert(path.clone(), arc_schema.clone());
+            let _ = self.compiling_schemas.insert(key.clone());
+            let schema = Schema::create(self, path, raw_schema);
+            let _ = self.compiling_schemas.remove(&key);
+            let arc_schema: Arc<Schema> = Arc::new(schema?);
+            let _ = self.schema_cache.insert(key, arc_schema.clone());
             Ok(arc_schema)
         }
     }
 
-    pub(in crate) fn build(&mut self) -> Scope {
-        self.schema_cache.values_mut().for_each(|schema_arc| {
-            #[allow(unsafe_code)]
-            unsafe { Arc::get_mut_unchecked(schema_arc) }.initialise();
+    // Referencing a schema that is being compiled would compile it again, endlessly
+    pub(in crate) fn is_compiling(&self, path: &Url) -> bool {
+        self.compiling_schemas.contains(&schema_cache_key(path))
+    }
+
+    /// Same as `schema`, but the raw schema is retrieved via the loader.
+    ///
+    /// # Errors
+    ///
+    /// The schema, or one of the schemas it references, is malformed or cannot be retrieved.
+    pub fn referenced_schema(&mut self, path: &Url) -> Result<Arc<Schema>, SchemaError>
+    where
+        T: 'static,
+    {
+        if let Some(cached_schema) = self.schema_cache.get(&schema_cache_key(path)) {
+            Ok(cached_schema.clone())
+        } else {
+            // The loader hands back the referenced portion of the document, which is compiled as is (no intermediate copies)
+            let referenced_raw_schema: Arc<T> = self.retrieve_schema(path)?;
+            self.schema(path, &*referenced_raw_schema)
+        }
+    }
+
+    /// Compile a schema embedded in a bigger document (ie. `/components/schemas/Pet` of an OpenAPI document).
+    ///
+    /// # Errors
+    ///
+    /// The schema, or one of the schemas it references, is malformed or cannot be retrieved.
+    pub fn schema_at_pointer(&mut self, document_path: &Url, json_pointer: &str) -> Result<Arc<Schema>, SchemaError>
+    where
+        T: 'static,
+    {
+        let mut path = document_path.clone();
+        path.set_fragment(Some(json_pointer));
+        self.referenced_schema(&path)
+    }
+
+    /// `$ref`s of the raw schema located by path (and of its subschemas) that cannot be resolved, as (location of the subschema, `$ref` value, error).
+    /// Values of the other keywords (ie. `enum` and `default`) are not schemas, so their `$ref`s are not references.
+    pub fn dangling_references<J: JsonType>(&mut self, path: &Url, raw_schema: &J) -> Vec<(Url, String, SchemaError)>
+    where
+        T: 'static,
+    {
+        let mut json_references = Vec::new();
+        walk_schema(path, raw_schema, &mut |schema_path, schema| {
+            if let Some(json_reference) = schema.get_attribute("$ref").and_then(|value| value.as_string()) {
+                json_references.push((schema_path.clone(), json_reference.to_string()));
+            }
         });
+        json_references
+            .into_iter()
+            .filter_map(|(schema_path, json_reference)| {
+                self.referenced_schema(&full_uri(&schema_path, &json_reference))
+                    .err()
+                    .map(|error| (schema_path, json_reference, error))
+            })
+            .collect()
+    }
+
+    /// Lint the raw schema addressed by path, which could have been already compiled, and the schemas it references.
+    ///
+    /// # Errors
+    ///
+    /// The raw schema, or one of the schemas it references, cannot be retrieved.
+    pub fn lint(&mut self, linter: &Linter, path: &Url) -> Result<Vec<LintDiagnostic>, SchemaError> {
+        let mut diagnostics = Vec::new();
+        let mut linted_paths: Vec<Url> = Vec::new();
+        let mut pending_paths = vec![path.clone()];
+        while let Some(schema_path) = pending_paths.pop() {
+            // Schemas referenced within already linted schemas (ie. recursive references) are linted once
+            if linted_paths.iter().any(|linted_path| is_within(linted_path, &schema_path)) {
+                continue;
+            }
+            let raw_schema: Arc<T> = self.retrieve_schema(&schema_path)?;
+            for diagnostic in linter.lint(&schema_path, &*raw_schema) {
+                if !diagnostics.contains(&diagnostic) {
+                    diagnostics.push(diagnostic);
+                }
+            }
+            pending_paths.extend(references(&schema_path, &*raw_schema));
+            linted_paths.push(schema_path);
+        }
+        Ok(diagnostics)
+    }
+
+    /// Initialise the schemas compiled so far, which are then usable for validation.
+    /// Recursive references are resolved here, as the referenced schemas are cached only once compiled.
+    pub fn build(&mut self) -> Scope {
+        self.schema_cache.values().for_each(|schema_arc| schema_arc.initialise(&self.schema_cache));
 
         Scope {
             draft_version: self.draft_version,
@@ -67,7 +304,7 @@ impl<T: JsonType> ScopeBuilder<T> {
 }
 
 #[cfg(test)]
-pub(in crate::types) fn scope_builder_create<A>(
+pub(in crate) fn scope_builder_create<A>(
     draft_version: DraftVersion,
     raw_schema: RustType,
     closure: &dyn Fn(&mut ScopeBuilder<RustType>, &Url, &RustType) -> A,
@@ -81,7 +318,7 @@ pub(in crate::types) fn scope_builder_create<A>(
 }
 
 #[cfg(test)]
-pub(in crate::types) fn scope_builder_create_and_build<A>(
+pub(in crate) fn scope_builder_create_and_build<A>(
     draft_version: DraftVersion,
     raw_schema: RustType,
     closure: &dyn Fn(&mut ScopeBuilder<RustType>, &Url, &RustType) -> A,
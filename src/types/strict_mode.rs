@@ -0,0 +1,170 @@
+use crate::{
+    keywords::{draft_annotation_keyword_names, draft_keyword_names},
+    types::{draft_version::DraftVersion, schema_warning::SchemaWarning},
+};
+use json_trait_rs::{JsonMapTrait, JsonType};
+use strum::IntoEnumIterator;
+use url::Url;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrictMode {
+    // Keywords that are not validated are silently skipped
+    Disabled,
+    // Keywords that are not validated are collected as ScopeBuilder warnings
+    Lenient,
+    // The first keyword that is not validated fails the schema compilation
+    Strict,
+}
+
+impl Default for StrictMode {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+// Keywords that are still meaningful next to `$ref`, as they are only used to locate other schemas
+const REF_SIBLINGS_ALLOWLIST: &[&str] = &["$ref", "definitions"];
+
+fn is_extension(draft_version: DraftVersion, keyword: &str) -> bool {
+    // OpenAPI allows specification extensions everywhere
+    draft_version == DraftVersion::OpenApi30 && keyword.starts_with("x-")
+}
+
+// validated_keywords are the draft keywords compiled into validators, the other keywords of the draft are ignored
+pub(in crate) fn keyword_warnings<J: JsonType>(
+    draft_version: DraftVersion,
+    validated_keywords: &[&str],
+    custom_keywords: &[&str],
+    path: &Url,
+    raw_schema: &J,
+) -> Vec<SchemaWarning> {
+    let schema_object = if let Some(value) = raw_schema.as_object() {
+        value
+    } else {
+        // non object schemas are reported by Schema::create so we're done here
+        return Vec::new();
+    };
+    let has_ref = raw_schema.get_attribute("$ref").is_some();
+
+    schema_object
+        .items()
+        .filter_map(|(keyword, _)| {
+            if custom_keywords.contains(&keyword) || is_extension(draft_version, keyword) {
+                None
+            } else if !draft_keyword_names(draft_version).contains(&keyword) {
+                Some(
+                    if DraftVersion::iter().any(|other_draft_version| draft_keyword_names(other_draft_version).contains(&keyword)) {
+                        SchemaWarning::UnsupportedKeyword {
+                            path: path.clone(),
+                            keyword: keyword.to_string(),
+                            draft_version,
+                        }
+                    } else {
+                        SchemaWarning::UnknownKeyword {
+                            path: path.clone(),
+                            keyword: keyword.to_string(),
+                        }
+                    },
+                )
+            } else if has_ref && !REF_SIBLINGS_ALLOWLIST.contains(&keyword) {
+                Some(SchemaWarning::IgnoredRefSibling {
+                    path: path.clone(),
+                    keyword: keyword.to_string(),
+                })
+            } else if !validated_keywords.contains(&keyword) && !draft_annotation_keyword_names(draft_version).contains(&keyword) {
+                Some(SchemaWarning::IgnoredKeyword {
+                    path: path.clone(),
+                    keyword: keyword.to_string(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StrictMode;
+    use crate::types::{
+        draft_version::DraftVersion,
+        schema::{initialised_draft4_schema, Schema},
+        schema_error::SchemaError,
+        schema_warning::SchemaWarning,
+        scope_builder::scope_builder_create_and_build,
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use test_case::test_case;
+
+    fn strict_schema(draft_version: DraftVersion, strict_mode: StrictMode, raw_schema: RustType) -> (Result<Schema, SchemaError>, Vec<SchemaWarning>) {
+        scope_builder_create_and_build(draft_version, raw_schema, &|scope_builder, generated_url, raw_schema| {
+            let schema = Schema::create(scope_builder.use_strict_mode(strict_mode), generated_url, raw_schema);
+            (schema, scope_builder.warnings().to_vec())
+        })
+    }
+
+    fn keyword(schema_warning: &SchemaWarning) -> &str {
+        match schema_warning {
+            SchemaWarning::UnknownKeyword { keyword, .. }
+            | SchemaWarning::UnsupportedKeyword { keyword, .. }
+            | SchemaWarning::IgnoredRefSibling { keyword, .. }
+            | SchemaWarning::IgnoredKeyword { keyword, .. } => keyword,
+        }
+    }
+
+    #[test_case(DraftVersion::Draft4, rust_type!({"title": "A", "type": "object", "properties": {"a": {"default": 1}}, "additionalProperties": false}))]
+    #[test_case(DraftVersion::Draft4, rust_type!({"definitions": {"a": {}}, "$ref": "#/definitions/a"}))]
+    #[test_case(DraftVersion::OpenApi30, rust_type!({"type": "string", "nullable": true, "x-internal": true}))]
+    fn compile_valid_schema(draft_version: DraftVersion, raw_schema: RustType) {
+        let (schema, warnings) = strict_schema(draft_version, StrictMode::Strict, raw_schema);
+        assert!(schema.is_ok());
+        assert!(warnings.is_empty());
+    }
+
+    #[test_case(DraftVersion::Draft4, rust_type!({"maxLenght": 1}), "maxLenght" => "UnknownKeyword")]
+    #[test_case(DraftVersion::Draft4, rust_type!({"x-internal": true}), "x-internal" => "UnknownKeyword")]
+    #[test_case(DraftVersion::Draft4, rust_type!({"nullable": true}), "nullable" => "UnsupportedKeyword")]
+    #[test_case(DraftVersion::OpenApi30, rust_type!({"definitions": {}}), "definitions" => "UnsupportedKeyword")]
+    #[test_case(DraftVersion::Draft4, rust_type!({"definitions": {"a": {}}, "$ref": "#/definitions/a", "type": "string"}), "type" => "IgnoredRefSibling")]
+    #[test_case(DraftVersion::Draft4, rust_type!({"type": "string", "maxLength": 1}), "maxLength" => "IgnoredKeyword")]
+    #[test_case(DraftVersion::OpenApi30, rust_type!({"type": "object", "required": ["a"]}), "required" => "IgnoredKeyword")]
+    fn compile_invalid_schema(draft_version: DraftVersion, raw_schema: RustType, expected_keyword: &str) -> &'static str {
+        match strict_schema(draft_version, StrictMode::Strict, raw_schema).0 {
+            Err(SchemaError::Strict(schema_warning)) => {
+                assert_eq!(keyword(&schema_warning), expected_keyword);
+                match schema_warning {
+                    SchemaWarning::UnknownKeyword { .. } => "UnknownKeyword",
+                    SchemaWarning::UnsupportedKeyword { .. } => "UnsupportedKeyword",
+                    SchemaWarning::IgnoredRefSibling { .. } => "IgnoredRefSibling",
+                    SchemaWarning::IgnoredKeyword { .. } => "IgnoredKeyword",
+                }
+            }
+            other => panic!("Expected strict mode error, received {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lenient_mode_collects_warnings_of_all_the_compiled_schemas() {
+        let (schema, warnings) = strict_schema(
+            DraftVersion::Draft4,
+            StrictMode::Lenient,
+            rust_type!({"properties": {"name": {"type": "string", "maxLenght": 1}}, "additionalProperty": false}),
+        );
+        assert!(schema.is_ok());
+        let mut keywords = warnings.iter().map(keyword).collect::<Vec<_>>();
+        keywords.sort_unstable();
+        assert_eq!(keywords, vec!["additionalProperty", "maxLenght"]);
+    }
+
+    #[test_case(StrictMode::Disabled)]
+    #[test_case(StrictMode::Lenient)]
+    fn non_strict_modes_compile_schema(strict_mode: StrictMode) {
+        assert!(strict_schema(DraftVersion::Draft4, strict_mode, rust_type!({"maxLenght": 1})).0.is_ok());
+    }
+
+    #[test]
+    fn ref_siblings_are_not_validated() {
+        let schema = initialised_draft4_schema(rust_type!({"definitions": {"a": {}}, "$ref": "#/definitions/a", "type": "string"})).expect("Schema is supposed to be valid");
+        assert!(schema.is_valid("#", &rust_type!(1)));
+    }
+}
@@ -0,0 +1,764 @@
+// Rust types are generated walking the compiled schema tree (`$ref`, `allOf`, `properties`, `additionalProperties` and `type`).
+// The keywords not retained by the compiled schemas (`required`, `enum`, `oneOf`, `items` and the string constraints) are read from
+// the raw schemas retrieved via the ScopeBuilder, so local and remote definitions are resolved once and become shared types.
+// The generated source depends on serde (derives) and serde_json (`serde_json::Value` is used for schemas without a precise type).
+use crate::{
+    keywords::DraftValidator,
+    types::{schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder},
+};
+use json_trait_rs::{JsonType, PrimitiveType};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::Path,
+    sync::Arc,
+};
+use url::Url;
+
+const SERDE_DERIVES: [&str; 2] = ["serde::Deserialize", "serde::Serialize"];
+const DEFAULT_DERIVES: [&str; 3] = ["Clone", "Debug", "PartialEq"];
+// Keywords that cannot be used as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 5] = ["crate", "self", "Self", "super", "_"];
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try",
+    "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+// Generated types must not shadow the types used by the generated source
+const RESERVED_TYPE_NAMES: [&str; 8] = ["Box", "HashMap", "Option", "Result", "Self", "String", "Value", "Vec"];
+const STRING_CONSTRAINTS: [&str; 4] = ["format", "maxLength", "minLength", "pattern"];
+
+// Acronyms are single words, the last uppercase letter of an acronym followed by lowercase ones starts a new word (ie. `HTTPServer`)
+fn words(name: &str) -> Vec<String> {
+    let characters = name.chars().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut previous_is_lowercase = false;
+    let mut previous_is_uppercase = false;
+    for (index, character) in characters.iter().copied().enumerate() {
+        if !character.is_ascii_alphanumeric() {
+            result.push(std::mem::take(&mut current));
+            previous_is_lowercase = false;
+            previous_is_uppercase = false;
+            continue;
+        }
+        let next_is_lowercase = characters.get(index + 1).map_or(false, char::is_ascii_lowercase);
+        if character.is_ascii_uppercase() && (previous_is_lowercase || (previous_is_uppercase && next_is_lowercase)) {
+            result.push(std::mem::take(&mut current));
+        }
+        previous_is_lowercase = character.is_ascii_lowercase() || character.is_ascii_digit();
+        previous_is_uppercase = character.is_ascii_uppercase();
+        current.push(character);
+    }
+    result.push(current);
+    result.into_iter().filter(|word| !word.is_empty()).collect()
+}
+
+fn pascal_case(name: &str) -> String {
+    let result = words(name)
+        .iter()
+        .map(|word| word[..1].to_ascii_uppercase() + &word[1..].to_ascii_lowercase())
+        .collect::<String>();
+    if result.starts_with(|character: char| character.is_ascii_digit()) {
+        format!("Value{}", result)
+    } else {
+        result
+    }
+}
+
+fn field_name(property_name: &str) -> String {
+    let result = words(property_name).iter().map(|word| word.to_ascii_lowercase()).collect::<Vec<_>>().join("_");
+    if result.is_empty() || result.starts_with(|character: char| character.is_ascii_digit()) {
+        format!("field_{}", result).trim_end_matches('_').to_string()
+    } else if NON_RAW_KEYWORDS.contains(&result.as_str()) {
+        format!("{}_", result)
+    } else if KEYWORDS.contains(&result.as_str()) {
+        format!("r#{}", result)
+    } else {
+        result
+    }
+}
+
+// Name of the shared type of a referenced schema, from the last segment of the JSON pointer or from the document name
+fn referenced_type_name(url: &Url) -> String {
+    let name = match url.fragment().and_then(|fragment| fragment.rsplit('/').find(|segment| !segment.is_empty())) {
+        Some(segment) => segment.to_string(),
+        None => url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or("")
+            .split('.')
+            .next()
+            .unwrap_or("")
+            .to_string(),
+    };
+    let type_name = pascal_case(&name);
+    if type_name.is_empty() {
+        "Schema".to_string()
+    } else {
+        type_name
+    }
+}
+
+fn subschema_path(schema_path: &Url, suffix: &str) -> Url {
+    let mut result = schema_path.clone();
+    result.set_fragment(Some(&format!("{}/{}", schema_path.fragment().unwrap_or("").trim_end_matches('/'), suffix)));
+    result
+}
+
+fn doc_comment<J: JsonType>(raw_schema: &J, indentation: &str) -> String {
+    ["title", "description"]
+        .iter()
+        .filter_map(|keyword| raw_schema.get_attribute(keyword).and_then(|value| value.as_string()))
+        .flat_map(str::lines)
+        .map(|line| format!("{}/// {}\n", indentation, line).replace(" \n", "\n"))
+        .collect()
+}
+
+// Schemas applying to the same value: the schema itself and the schemas reached via `$ref` and `allOf`
+fn applied_schemas(schema: &Arc<Schema>, schemas: &mut Vec<Arc<Schema>>) {
+    if schemas.iter().any(|applied_schema| applied_schema.path == schema.path) {
+        return;
+    }
+    schemas.push(schema.clone());
+    for validator in &schema.validators {
+        match validator {
+            DraftValidator::Ref(ref_validator) => ref_validator
+                .referenced_schema()
+                .iter()
+                .for_each(|referenced_schema| applied_schemas(referenced_schema, schemas)),
+            DraftValidator::AllOf(all_of_validator) => all_of_validator.schemas.iter().for_each(|schema| applied_schemas(schema, schemas)),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TypeGenerator {
+    derives: Vec<String>,
+    // Shared types, by the path of their schema
+    type_names: BTreeMap<Url, String>,
+    // Paths of the shared types being defined, references to them are recursive
+    defining_paths: HashSet<Url>,
+    used_type_names: HashSet<String>,
+    definitions: Vec<String>,
+}
+
+impl Default for TypeGenerator {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+impl TypeGenerator {
+    #[must_use]
+    pub fn create() -> Self {
+        Self {
+            derives: DEFAULT_DERIVES.iter().chain(SERDE_DERIVES.iter()).map(ToString::to_string).collect(),
+            type_names: BTreeMap::new(),
+            defining_paths: HashSet::new(),
+            used_type_names: RESERVED_TYPE_NAMES.iter().map(ToString::to_string).collect(),
+            definitions: Vec::new(),
+        }
+    }
+
+    /// Derives of the generated types, serde ones are always present as the generated types use serde attributes.
+    pub fn use_derives(&mut self, derives: &[&str]) -> &mut Self {
+        self.derives = derives.iter().chain(SERDE_DERIVES.iter()).map(ToString::to_string).collect();
+        self
+    }
+
+    /// Generate the types of schema (compiled by scope_builder) and of the schemas referenced by it, schema is the type named type_name.
+    /// Types of schemas already generated by previous calls are reused, so the same generator could be used for multiple schemas.
+    ///
+    /// # Errors
+    ///
+    /// The raw schemas cannot be retrieved via the loader of scope_builder, or the referenced schemas cannot be compiled.
+    pub fn generate<T: 'static + JsonType>(&mut self, scope_builder: &mut ScopeBuilder<T>, type_name: &str, schema: &Arc<Schema>) -> Result<String, SchemaError> {
+        self.shared_type(scope_builder, schema, type_name)
+    }
+
+    /// Source of the generated types, meant to be written by build scripts and `include!`d.
+    #[must_use]
+    pub fn source(&self) -> String {
+        let mut source = "// Generated by jsonschema-validator, do not edit\n".to_string();
+        for definition in &self.definitions {
+            source.push('\n');
+            source.push_str(definition);
+        }
+        source
+    }
+
+    /// The file is written only if its content changed, so the including crate is not needlessly rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// The file cannot be written.
+    pub fn write_source<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let source = self.source();
+        if fs::read_to_string(&path).ok().as_ref() == Some(&source) {
+            Ok(())
+        } else {
+            fs::write(path, source)
+        }
+    }
+
+    fn unique_type_name(&mut self, type_name_hint: &str) -> String {
+        let type_name_hint = if type_name_hint.is_empty() { "Schema" } else { type_name_hint };
+        let mut type_name = type_name_hint.to_string();
+        let mut suffix = 1;
+        while self.used_type_names.contains(&type_name) {
+            suffix += 1;
+            type_name = format!("{}{}", type_name_hint, suffix);
+        }
+        let _ = self.used_type_names.insert(type_name.clone());
+        type_name
+    }
+
+    fn derive_attribute(&self) -> String {
+        format!("#[derive({})]\n", self.derives.join(", "))
+    }
+
+    fn shared_type<T: 'static + JsonType>(&mut self, scope_builder: &mut ScopeBuilder<T>, schema: &Arc<Schema>, type_name_hint: &str) -> Result<String, SchemaError> {
+        if let Some(type_name) = self.type_names.get(&schema.path) {
+            // Recursive types would have infinite size, so references to the types being defined are boxed
+            return Ok(if self.defining_paths.contains(&schema.path) {
+                format!("Box<{}>", type_name)
+            } else {
+                type_name.clone()
+            });
+        }
+
+        let type_name = self.unique_type_name(&pascal_case(type_name_hint));
+        let _ = self.type_names.insert(schema.path.clone(), type_name.clone());
+        let _ = self.defining_paths.insert(schema.path.clone());
+        let rust_type = self.schema_type(scope_builder, schema, Some(&type_name), &type_name);
+        let _ = self.defining_paths.remove(&schema.path);
+        let rust_type = rust_type?;
+        if rust_type == format!("Option<{}>", type_name) {
+            // Nullable schemas with a definition on their own, the definition is shared and references are optional
+            let _ = self.type_names.insert(schema.path.clone(), rust_type.clone());
+            Ok(rust_type)
+        } else {
+            if rust_type != type_name {
+                // The schema does not need a definition on its own (ie. `{"type": "string"}`), the alias keeps the type shared
+                self.definitions.push(format!("pub type {} = {};\n", type_name, rust_type));
+            }
+            Ok(type_name)
+        }
+    }
+
+    // Rust type of the values valid against schema, types defined for it are named type_name (if any) or after type_name_hint
+    fn schema_type<T: 'static + JsonType>(
+        &mut self,
+        scope_builder: &mut ScopeBuilder<T>,
+        schema: &Arc<Schema>,
+        type_name: Option<&str>,
+        type_name_hint: &str,
+    ) -> Result<String, SchemaError> {
+        // Keywords next to `$ref` are ignored, so the schema is the referenced one
+        if let Some(ref_validator) = schema.validators.iter().find_map(|validator| match validator {
+            DraftValidator::Ref(ref_validator) => Some(ref_validator),
+            _ => None,
+        }) {
+            // Recursive references are resolved by ScopeBuilder::build, before that the referenced schema is in the cache already
+            let referenced_schema = match ref_validator.referenced_schema() {
+                Some(referenced_schema) => referenced_schema,
+                None => scope_builder.referenced_schema(&ref_validator.referenced_uri)?,
+            };
+            return self.shared_type(scope_builder, &referenced_schema, &referenced_type_name(&ref_validator.referenced_uri));
+        }
+
+        let raw_schema: Arc<T> = scope_builder.retrieve_schema(&schema.path)?;
+        let mut schemas = Vec::new();
+        applied_schemas(schema, &mut schemas);
+        let mut types = schemas
+            .iter()
+            .flat_map(|applied_schema| &applied_schema.validators)
+            .find_map(|validator| match validator {
+                DraftValidator::Type(type_validator) => Some(type_validator.types.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let is_object_schema = schemas.iter().flat_map(|applied_schema| &applied_schema.validators).any(|validator| {
+            matches!(
+                validator,
+                DraftValidator::Properties(_) | DraftValidator::PatternProperties(_) | DraftValidator::AdditionalProperties(_)
+            )
+        });
+        if types.is_empty() && is_object_schema {
+            let _ = types.insert(PrimitiveType::Object);
+        }
+        let mut is_nullable = types.len() > 1 && types.remove(&PrimitiveType::Null);
+
+        let rust_type = if let Some((values, has_null)) = string_enum_values(&*raw_schema) {
+            is_nullable = has_null;
+            self.define_enum(&*raw_schema, type_name, type_name_hint, &values)
+        } else if raw_schema.get_attribute("oneOf").and_then(|value| value.as_array()).is_some() {
+            self.define_one_of(scope_builder, schema, &*raw_schema, type_name, type_name_hint)?
+        } else if types.len() == 1 {
+            match types.iter().next() {
+                Some(PrimitiveType::Array) => self.array_type(scope_builder, schema, &*raw_schema, type_name_hint)?,
+                Some(PrimitiveType::Boolean) => "bool".to_string(),
+                Some(PrimitiveType::Integer) => "i64".to_string(),
+                Some(PrimitiveType::Null) => "()".to_string(),
+                Some(PrimitiveType::Number) => "f64".to_string(),
+                Some(PrimitiveType::Object) => self.object_type(scope_builder, &schemas, &*raw_schema, type_name, type_name_hint)?,
+                Some(PrimitiveType::String) if STRING_CONSTRAINTS.iter().any(|keyword| raw_schema.get_attribute(keyword).is_some()) => {
+                    self.define_constrained_string(&*raw_schema, type_name, type_name_hint)
+                }
+                _ => "String".to_string(),
+            }
+        } else if types.len() == 2 && types.contains(&PrimitiveType::Integer) && types.contains(&PrimitiveType::Number) {
+            "f64".to_string()
+        } else {
+            "serde_json::Value".to_string()
+        };
+
+        Ok(if is_nullable && !rust_type.starts_with("Option<") {
+            format!("Option<{}>", rust_type)
+        } else {
+            rust_type
+        })
+    }
+
+    fn definition_name(&mut self, type_name: Option<&str>, type_name_hint: &str) -> String {
+        type_name.map_or_else(|| self.unique_type_name(&pascal_case(type_name_hint)), ToString::to_string)
+    }
+
+    fn define_enum<J: JsonType>(&mut self, raw_schema: &J, type_name: Option<&str>, type_name_hint: &str, values: &[String]) -> String {
+        let type_name = self.definition_name(type_name, type_name_hint);
+        let mut definition = doc_comment(raw_schema, "");
+        definition.push_str(&self.derive_attribute());
+        definition.push_str(&format!("pub enum {} {{\n", type_name));
+        let mut variant_names = HashSet::new();
+        for value in values {
+            let mut variant_name = pascal_case(value);
+            if variant_name.is_empty() {
+                variant_name = "Empty".to_string();
+            }
+            while !variant_names.insert(variant_name.clone()) {
+                variant_name.push('_');
+            }
+            definition.push_str(&format!("    #[serde(rename = {:?})]\n    {},\n", value, variant_name));
+        }
+        definition.push_str("}\n");
+        self.definitions.push(definition);
+        type_name
+    }
+
+    fn define_one_of<T: 'static + JsonType>(
+        &mut self,
+        scope_builder: &mut ScopeBuilder<T>,
+        schema: &Arc<Schema>,
+        raw_schema: &T,
+        type_name: Option<&str>,
+        type_name_hint: &str,
+    ) -> Result<String, SchemaError> {
+        let type_name = self.definition_name(type_name, type_name_hint);
+        let variants_count = raw_schema.get_attribute("oneOf").and_then(|value| value.as_array()).map_or(0, Iterator::count);
+        let mut variants = Vec::new();
+        for index in 0..variants_count {
+            // oneOf is not compiled yet, so the variants are compiled on their own (resolving their references)
+            let variant_schema = scope_builder.referenced_schema(&subschema_path(&schema.path, &format!("oneOf/{}", index)))?;
+            let variant_type = self.schema_type(scope_builder, &variant_schema, None, &format!("{}Variant{}", type_name, index))?;
+            // Variants of generated types are named after them
+            let is_generated_type = variant_type.starts_with(|character: char| character.is_ascii_uppercase())
+                && variant_type.chars().all(|character| character.is_ascii_alphanumeric())
+                && variant_type != "String";
+            let variant_name = if is_generated_type { variant_type.clone() } else { format!("Variant{}", index) };
+            variants.push((variant_name, variant_type));
+        }
+
+        let mut definition = doc_comment(raw_schema, "");
+        definition.push_str(&self.derive_attribute());
+        definition.push_str(&format!("#[serde(untagged)]\npub enum {} {{\n", type_name));
+        let mut variant_names = HashSet::new();
+        for (mut variant_name, variant_type) in variants {
+            while !variant_names.insert(variant_name.clone()) {
+                variant_name.push('_');
+            }
+            definition.push_str(&format!("    {}({}),\n", variant_name, variant_type));
+        }
+        definition.push_str("}\n");
+        self.definitions.push(definition);
+        Ok(type_name)
+    }
+
+    fn define_constrained_string<J: JsonType>(&mut self, raw_schema: &J, type_name: Option<&str>, type_name_hint: &str) -> String {
+        let type_name = self.definition_name(type_name, type_name_hint);
+        let mut definition = doc_comment(raw_schema, "");
+        // Constraints are listed as they are not enforced by the newtype itself, instances have to be validated against the schema
+        for keyword in &STRING_CONSTRAINTS {
+            if let Some(value) = raw_schema.get_attribute(keyword) {
+                let value = value
+                    .as_string()
+                    .map_or_else(|| value.to_rust_type().to_string(), |string_value| format!("{:?}", string_value));
+                definition.push_str(&format!("// {}: {}\n", keyword, value));
+            }
+        }
+        definition.push_str(&self.derive_attribute());
+        definition.push_str(&format!("#[serde(transparent)]\npub struct {}(pub String);\n", type_name));
+        self.definitions.push(definition);
+        type_name
+    }
+
+    fn array_type<T: 'static + JsonType>(
+        &mut self,
+        scope_builder: &mut ScopeBuilder<T>,
+        schema: &Arc<Schema>,
+        raw_schema: &T,
+        type_name_hint: &str,
+    ) -> Result<String, SchemaError> {
+        // Tuple validation (`items` as array) is not represented, its items could have any type
+        let item_type = if raw_schema.get_attribute("items").map_or(false, |value| value.is_object()) {
+            let items_schema = scope_builder.referenced_schema(&subschema_path(&schema.path, "items"))?;
+            self.schema_type(scope_builder, &items_schema, None, &format!("{}Item", type_name_hint))?
+        } else {
+            "serde_json::Value".to_string()
+        };
+        Ok(format!("Vec<{}>", unboxed(item_type)))
+    }
+
+    fn object_type<T: 'static + JsonType>(
+        &mut self,
+        scope_builder: &mut ScopeBuilder<T>,
+        schemas: &[Arc<Schema>],
+        raw_schema: &T,
+        type_name: Option<&str>,
+        type_name_hint: &str,
+    ) -> Result<String, SchemaError> {
+        // Properties (and their requiredness) are merged across `allOf` and `$ref`, the first declaration of a property wins
+        let mut properties = BTreeMap::new();
+        let mut required = HashSet::new();
+        for applied_schema in schemas {
+            let applied_raw_schema: Arc<T> = scope_builder.retrieve_schema(&applied_schema.path)?;
+            if let Some(required_items) = applied_raw_schema.get_attribute("required").and_then(|value| value.as_array()) {
+                required.extend(required_items.filter_map(|item| item.as_string().map(ToString::to_string)));
+            }
+            for validator in &applied_schema.validators {
+                if let DraftValidator::Properties(properties_validator) = validator {
+                    for (property_name, property_schema) in &properties_validator.properties {
+                        let _ = properties.entry(property_name.clone()).or_insert_with(|| property_schema.clone());
+                    }
+                }
+            }
+        }
+        // Only additionalProperties of the schema itself is considered, as it applies to the properties of its sibling keywords only
+        let additional_properties = schemas.first().and_then(|schema| {
+            schema.validators.iter().find_map(|validator| match validator {
+                DraftValidator::AdditionalProperties(additional_properties_validator) => Some(additional_properties_validator.schema.clone()),
+                _ => None,
+            })
+        });
+
+        let type_name_hint = type_name.unwrap_or(type_name_hint).to_string();
+        let additional_properties_type = match &additional_properties {
+            Some(Some(additional_properties_schema)) => Some(unboxed(self.schema_type(
+                scope_builder,
+                additional_properties_schema,
+                None,
+                &format!("{}Value", type_name_hint),
+            )?)),
+            Some(None) => None,
+            None if properties.is_empty() => Some("serde_json::Value".to_string()),
+            None => None,
+        };
+        if properties.is_empty() {
+            if let Some(additional_properties_type) = additional_properties_type {
+                return Ok(format!("std::collections::HashMap<String, {}>", additional_properties_type));
+            }
+        }
+
+        let mut fields = Vec::new();
+        for (property_name, property_schema) in properties {
+            let property_type = self.schema_type(scope_builder, &property_schema, None, &format!("{}{}", type_name_hint, pascal_case(&property_name)))?;
+            let property_raw_schema: Arc<T> = scope_builder.retrieve_schema(&property_schema.path)?;
+            fields.push((
+                property_name.clone(),
+                property_type,
+                required.contains(&property_name),
+                doc_comment(&*property_raw_schema, "    "),
+            ));
+        }
+
+        let type_name = self.definition_name(type_name, &type_name_hint);
+        let mut definition = doc_comment(raw_schema, "");
+        definition.push_str(&self.derive_attribute());
+        if matches!(additional_properties, Some(None)) {
+            definition.push_str("#[serde(deny_unknown_fields)]\n");
+        }
+        definition.push_str(&format!("pub struct {} {{\n", type_name));
+        let mut field_names = HashSet::new();
+        for (property_name, property_type, is_required, field_doc_comment) in fields {
+            let mut rust_field_name = field_name(&property_name);
+            while !field_names.insert(rust_field_name.clone()) {
+                rust_field_name.push('_');
+            }
+            let mut serde_attributes = Vec::new();
+            if rust_field_name.trim_start_matches("r#") != property_name {
+                serde_attributes.push(format!("rename = {:?}", property_name));
+            }
+            let property_type = if is_required {
+                property_type
+            } else {
+                serde_attributes.push("default".to_string());
+                serde_attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                if property_type.starts_with("Option<") {
+                    property_type
+                } else {
+                    format!("Option<{}>", property_type)
+                }
+            };
+            definition.push_str(&field_doc_comment);
+            if !serde_attributes.is_empty() {
+                definition.push_str(&format!("    #[serde({})]\n", serde_attributes.join(", ")));
+            }
+            definition.push_str(&format!("    pub {}: {},\n", rust_field_name, property_type));
+        }
+        if let Some(additional_properties_type) = additional_properties_type {
+            let mut rust_field_name = "additional_properties".to_string();
+            while field_names.contains(&rust_field_name) {
+                rust_field_name.push('_');
+            }
+            definition.push_str(&format!(
+                "    #[serde(flatten)]\n    pub {}: std::collections::HashMap<String, {}>,\n",
+                rust_field_name, additional_properties_type
+            ));
+        }
+        definition.push_str("}\n");
+        self.definitions.push(definition);
+        Ok(type_name)
+    }
+}
+
+// Items of collections are allocated on the heap already, so they do not need to be boxed
+fn unboxed(rust_type: String) -> String {
+    if rust_type.starts_with("Box<") && rust_type.ends_with('>') {
+        rust_type["Box<".len()..rust_type.len() - 1].to_string()
+    } else {
+        rust_type
+    }
+}
+
+// Values of `enum` if they are all strings, null is allowed as well (it makes the type optional)
+fn string_enum_values<J: JsonType>(raw_schema: &J) -> Option<(Vec<String>, bool)> {
+    let mut values = Vec::new();
+    let mut has_null = false;
+    for value in raw_schema.get_attribute("enum")?.as_array()? {
+        if let Some(string_value) = value.as_string() {
+            values.push(string_value.to_string());
+        } else if value.is_null() {
+            has_null = true;
+        } else {
+            return None;
+        }
+    }
+    if values.is_empty() {
+        None
+    } else {
+        Some((values, has_null))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_name, pascal_case, TypeGenerator};
+    use crate::types::{draft_version::DraftVersion, scope_builder::ScopeBuilder};
+    use json_trait_rs::{rust_type, RustType};
+    use loader_rs::{loaders::RustTypeLoader, LoaderTrait};
+    use std::sync::Arc;
+    use test_case::test_case;
+    use url::Url;
+
+    fn generate(raw_schema: RustType, remote_schemas: Vec<(&str, RustType)>) -> String {
+        let loader = RustTypeLoader::default();
+        for (url, remote_schema) in remote_schemas {
+            loader.save_in_cache(&Url::parse(url).unwrap(), &Arc::new(remote_schema));
+        }
+        let mut scope_builder = ScopeBuilder::create(DraftVersion::Draft4, loader);
+        let raw_schema = Arc::new(raw_schema);
+        let url = scope_builder.inject_schema(&raw_schema);
+        let schema = scope_builder.schema(&url, &*raw_schema).unwrap();
+        let mut type_generator = TypeGenerator::create();
+        let _ = type_generator.use_derives(&["Debug"]).generate(&mut scope_builder, "Root", &schema).unwrap();
+        type_generator.source()
+    }
+
+    #[test_case("user_id" => "UserId")]
+    #[test_case("userId" => "UserId")]
+    #[test_case("HTTPServer" => "HttpServer")]
+    #[test_case("userID" => "UserId")]
+    #[test_case("light-red" => "LightRed")]
+    #[test_case("2xx" => "Value2xx")]
+    fn test_pascal_case(name: &str) -> String {
+        pascal_case(name)
+    }
+
+    #[test_case("userId" => "user_id")]
+    #[test_case("first name" => "first_name")]
+    #[test_case("type" => "r#type")]
+    #[test_case("self" => "self_")]
+    #[test_case("$ref" => "r#ref" ; "keyword after sanitisation")]
+    #[test_case("2fa" => "field_2fa")]
+    #[test_case("" => "field")]
+    fn test_field_name(property_name: &str) -> String {
+        field_name(property_name)
+    }
+
+    #[test]
+    fn generate_struct_with_optional_properties_and_shared_definitions() {
+        assert_eq!(
+            generate(
+                rust_type!({
+                    "description": "A person",
+                    "definitions": {"address": {"type": "object", "properties": {"street": {"type": "string"}}, "required": ["street"]}},
+                    "type": "object",
+                    "properties": {
+                        "firstName": {"type": "string", "description": "Given name"},
+                        "age": {"type": ["integer", "null"]},
+                        "home": {"$ref": "#/definitions/address"},
+                        "work": {"$ref": "#/definitions/address"},
+                        "tags": {"type": "array", "items": {"type": "string"}},
+                    },
+                    "required": ["firstName", "age"],
+                    "additionalProperties": false,
+                }),
+                vec![]
+            ),
+            "// Generated by jsonschema-validator, do not edit
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Address {
+    pub street: String,
+}
+
+/// A person
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Root {
+    pub age: Option<i64>,
+    /// Given name
+    #[serde(rename = \"firstName\")]
+    pub first_name: String,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub home: Option<Address>,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub work: Option<Address>,
+}
+"
+        );
+    }
+
+    #[test]
+    fn generate_enums_newtypes_and_maps() {
+        assert_eq!(
+            generate(
+                rust_type!({
+                    "type": "object",
+                    "properties": {
+                        "colour": {"enum": ["light-red", "blue", null]},
+                        "code": {"type": "string", "pattern": "^[A-Z]{3}$"},
+                        "pet": {"oneOf": [{"$ref": "http://example.com/cat.json"}, {"type": "integer"}]},
+                        "labels": {"type": "object", "additionalProperties": {"type": "number"}},
+                    },
+                    "required": ["colour", "code", "pet", "labels"],
+                }),
+                vec![("http://example.com/cat.json", rust_type!({"properties": {"lives": {"type": "integer"}}}))]
+            ),
+            "// Generated by jsonschema-validator, do not edit
+
+// pattern: \"^[A-Z]{3}$\"
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct RootCode(pub String);
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub enum RootColour {
+    #[serde(rename = \"light-red\")]
+    LightRed,
+    #[serde(rename = \"blue\")]
+    Blue,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Cat {
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub lives: Option<i64>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum RootPet {
+    Cat(Cat),
+    Variant1(i64),
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Root {
+    pub code: RootCode,
+    pub colour: Option<RootColour>,
+    pub labels: std::collections::HashMap<String, f64>,
+    pub pet: RootPet,
+}
+"
+        );
+    }
+
+    #[test]
+    fn generate_struct_merging_all_of() {
+        assert_eq!(
+            generate(
+                rust_type!({
+                    "definitions": {"base": {"properties": {"id": {"type": "integer"}}, "required": ["id"]}},
+                    "allOf": [{"$ref": "#/definitions/base"}, {"properties": {"name": {"type": "string"}}, "required": ["name"]}],
+                }),
+                vec![]
+            ),
+            "// Generated by jsonschema-validator, do not edit
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Root {
+    pub id: i64,
+    pub name: String,
+}
+"
+        );
+    }
+
+    #[test]
+    fn generate_recursive_types() {
+        assert_eq!(
+            generate(
+                rust_type!({
+                    "properties": {
+                        "child": {"$ref": "#"},
+                        "children": {"type": "array", "items": {"$ref": "#"}},
+                        "named": {"type": "object", "additionalProperties": {"$ref": "#/definitions/node"}},
+                    },
+                    "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}, "required": ["next"]}},
+                }),
+                vec![]
+            ),
+            "// Generated by jsonschema-validator, do not edit
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Node {
+    pub next: Box<Node>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct Root {
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub child: Option<Box<Root>>,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub children: Option<Vec<Root>>,
+    #[serde(default, skip_serializing_if = \"Option::is_none\")]
+    pub named: Option<std::collections::HashMap<String, Node>>,
+}
+"
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use crate::types::keyword_type::KeywordType;
 use std::fmt::{Display, Error, Formatter};
 
+/// Reason for an instance not being valid against a schema.
 #[derive(Clone, Debug, PartialEq)]
-pub(in crate) struct ValidationError {
+pub struct ValidationError {
     // TODO: enhance content
     message: String,
     keyword: KeywordType,
@@ -16,23 +17,38 @@ impl Display for ValidationError {
 }
 
 fn normalise_path(path: &str) -> &str {
-    if path.eq("#/") {
-        "#"
-    } else if path.ends_with('/') {
-        path.strip_suffix('/').unwrap()
-    } else {
-        path
-    }
+    // "#/" is normalised to "#" as well, as the document root does not need a trailing /
+    path.strip_suffix('/').unwrap_or(path)
 }
 
 impl ValidationError {
-    pub(in crate) fn new(path: &str, keyword: KeywordType, message: &str) -> Self {
+    /// The trailing `/` of `path` is dropped, so `#/` and `#` are the same location.
+    #[must_use]
+    pub fn new(path: &str, keyword: KeywordType, message: &str) -> Self {
         Self {
             path: normalise_path(path).to_string(),
             message: message.to_string(),
             keyword,
         }
     }
+
+    /// Location of the invalid value within the instance (ie. `#/items/0`).
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Keyword that the value does not satisfy.
+    #[must_use]
+    pub const fn keyword(&self) -> KeywordType {
+        self.keyword
+    }
+
+    /// Human readable description of the error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 #[cfg(test)]
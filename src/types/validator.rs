@@ -1,9 +1,10 @@
-use crate::types::{keyword_type::KeywordType, schema::Schema, schema_error::SchemaError, scope_builder::ScopeBuilder, validator_error_iterator::ValidationErrorIterator};
-use json_trait_rs::JsonType;
+use crate::types::{annotation::Annotation, keyword_type::KeywordType, schema_error::SchemaError, scope_builder::ScopeBuilder, validator_error_iterator::ValidationErrorIterator};
+use json_trait_rs::{JsonType, RustType};
 use std::{any::Any, fmt::Debug};
+use url::Url;
 
 pub(in crate) trait Validator: Debug + Sync + Send {
-    fn compile<T: 'static + JsonType>(scope_builder: &mut ScopeBuilder<T>, schema: &Schema) -> Result<Option<Self>, SchemaError>
+    fn compile<T: 'static + JsonType, J: JsonType>(scope_builder: &mut ScopeBuilder<T>, path: &Url, raw_schema: &J) -> Result<Option<Self>, SchemaError>
     where
         Self: Sized;
 
@@ -15,5 +16,26 @@ pub(in crate) trait Validator: Debug + Sync + Send {
         self.validation_errors(path, value).next().is_none()
     }
 
+    // Same as validation_errors, but large objects are split across the rayon thread pool
+    #[cfg(feature = "rayon")]
+    fn par_validation_errors<T: 'static + JsonType + Sync>(&self, path: &str, value: &T) -> ValidationErrorIterator {
+        self.validation_errors(path, value)
+    }
+
+    // Fill the missing parts of value with the `default`s of the subschemas (if any)
+    fn apply_defaults(&self, _value: &mut RustType) {}
+
+    // Convert value, if possible without loss of information, into a value accepted by the keyword
+    fn coerce(&self, _value: &mut RustType) {}
+
+    // Remove from value the properties that are not allowed by the keyword
+    fn sanitise(&self, _value: &mut RustType) {}
+
+    // Collect the annotations of the subschemas applied to value and return whether value is valid against the keyword.
+    // Keywords with subschemas derive their validity from the collection, so that each subschema is evaluated only once
+    fn collect_annotations<T: 'static + JsonType>(&self, path: &str, value: &T, _annotations: &mut Vec<Annotation>) -> bool {
+        self.is_valid(path, value)
+    }
+
     fn as_any(&self) -> &dyn Any;
 }
@@ -1,8 +1,10 @@
 use crate::types::validation_error::ValidationError;
 use std::ops::{Deref, DerefMut};
 
+/// Validation errors of an instance, an empty iterator means that the instance is valid.
 #[allow(missing_debug_implementations)] // No debug implementation to avoid to consume the iterator
-pub(in crate) struct ValidationErrorIterator(Box<dyn Iterator<Item = ValidationError>>);
+#[must_use = "validation errors are reported only if the iterator is consumed"]
+pub struct ValidationErrorIterator(Box<dyn Iterator<Item = ValidationError>>);
 
 impl Iterator for ValidationErrorIterator {
     type Item = ValidationError;
@@ -33,7 +35,7 @@ impl From<ValidationError> for ValidationErrorIterator {
 }
 
 impl ValidationErrorIterator {
-    pub(in crate) fn new<I: 'static + IntoIterator<Item = ValidationError>>(value: I) -> Self {
+    pub fn new<I: 'static + IntoIterator<Item = ValidationError>>(value: I) -> Self {
         Self(Box::new(value.into_iter()))
     }
 }
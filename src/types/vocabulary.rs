@@ -0,0 +1,216 @@
+use crate::types::{
+    custom_keyword::{CustomKeyword, CustomKeywordRegistration},
+    keyword_type::KeywordType,
+    schema_error::SchemaError,
+};
+use json_trait_rs::{JsonMapTrait, JsonType};
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    pub(in crate) uri: Url,
+    pub(in crate) keywords: HashSet<KeywordType>,
+    pub(in crate) custom_keywords: Vec<CustomKeywordRegistration>,
+}
+
+impl Vocabulary {
+    #[must_use]
+    pub fn create(uri: Url) -> Self {
+        Self {
+            uri,
+            keywords: HashSet::new(),
+            custom_keywords: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn keyword(mut self, keyword_type: KeywordType) -> Self {
+        let _ = self.keywords.insert(keyword_type);
+        self
+    }
+
+    #[must_use]
+    pub fn custom_keyword<K: 'static + CustomKeyword>(mut self, keyword: &'static str) -> Self {
+        self.custom_keywords.push(CustomKeywordRegistration::new::<K>(keyword, None));
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(in crate) struct Dialect {
+    pub(in crate) meta_schema: Url,
+    pub(in crate) keywords: HashSet<KeywordType>,
+    pub(in crate) custom_keywords: Vec<CustomKeywordRegistration>,
+}
+
+fn malformed_vocabulary(path: &Url, detail: String) -> SchemaError {
+    SchemaError::Malformed {
+        path: path.clone(),
+        keyword: KeywordType::Vocabulary,
+        detail,
+    }
+}
+
+impl Dialect {
+    pub(in crate) fn create<J: JsonType>(meta_schema: &Url, raw_meta_schema: &J, vocabularies: &HashMap<Url, Vocabulary>) -> Result<Self, SchemaError> {
+        let vocabulary_map = if let Some(value) = raw_meta_schema.get_attribute("$vocabulary").and_then(|value| value.as_object()) {
+            value
+        } else {
+            return Err(malformed_vocabulary(meta_schema, "Meta-schema has to declare `$vocabulary` as an object.".to_string()));
+        };
+
+        let mut dialect = Self {
+            meta_schema: meta_schema.clone(),
+            keywords: HashSet::new(),
+            custom_keywords: Vec::new(),
+        };
+        for (uri, is_required) in vocabulary_map.items() {
+            let is_required = if let Some(value) = is_required.as_boolean() {
+                value
+            } else {
+                return Err(malformed_vocabulary(meta_schema, format!("Value associated to vocabulary `{}` has to be a boolean.", uri)));
+            };
+
+            // Optional vocabularies that are not registered are ignored
+            if let Some(vocabulary) = vocabularies.get(&Url::parse(uri)?) {
+                dialect.keywords.extend(vocabulary.keywords.iter().copied());
+                dialect.custom_keywords.extend(vocabulary.custom_keywords.iter().copied());
+            } else if is_required {
+                return Err(malformed_vocabulary(meta_schema, format!("Required vocabulary `{}` is not registered.", uri)));
+            }
+        }
+        Ok(dialect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dialect, Vocabulary};
+    use crate::{
+        hash_set,
+        iterator_utils::empty_iterator::EmptyIterator,
+        types::{
+            custom_keyword::CustomKeyword, draft_version::DraftVersion, instance::Instance, keyword_type::KeywordType, schema::Schema, schema_error::SchemaError,
+            scope_builder::scope_builder_create_and_build, validation_error::ValidationError, validator_error_iterator::ValidationErrorIterator,
+        },
+    };
+    use json_trait_rs::{rust_type, RustType};
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    };
+    use test_case::test_case;
+    use url::Url;
+
+    // Testing constants
+    fn vocabularies() -> HashMap<Url, Vocabulary> {
+        vec![
+            Vocabulary::create(Url::parse("https://example.com/vocab/types").unwrap()).keyword(KeywordType::Type),
+            Vocabulary::create(Url::parse("https://example.com/vocab/objects").unwrap()).keyword(KeywordType::Properties),
+        ]
+        .into_iter()
+        .map(|vocabulary| (vocabulary.uri.clone(), vocabulary))
+        .collect()
+    }
+
+    fn meta_schema_url() -> Url {
+        Url::parse("memory:///meta-schema").unwrap()
+    }
+
+    // Tests
+    #[test_case(
+        rust_type!({"$vocabulary": {"https://example.com/vocab/types": true, "https://example.com/vocab/objects": false}}),
+        &hash_set![KeywordType::Type, KeywordType::Properties]
+    )]
+    #[test_case(
+        rust_type!({"$vocabulary": {"https://example.com/vocab/types": true}}),
+        &hash_set![KeywordType::Type]
+    )]
+    #[test_case(
+        rust_type!({"$vocabulary": {"https://example.com/vocab/types": true, "https://example.com/vocab/unknown": false}}),
+        &hash_set![KeywordType::Type] ;
+        "unknown optional vocabulary is ignored"
+    )]
+    fn create_dialect_valid(raw_meta_schema: RustType, expected_keywords: &HashSet<KeywordType>) {
+        let dialect = Dialect::create(&meta_schema_url(), &raw_meta_schema, &vocabularies()).expect("Dialect is supposed to be valid");
+        assert_eq!(&dialect.keywords, expected_keywords);
+    }
+
+    #[test_case(
+        rust_type!({}),
+        "Meta-schema has to declare `$vocabulary` as an object."
+    )]
+    #[test_case(
+        rust_type!({"$vocabulary": {"https://example.com/vocab/types": 1}}),
+        "Value associated to vocabulary `https://example.com/vocab/types` has to be a boolean."
+    )]
+    #[test_case(
+        rust_type!({"$vocabulary": {"https://example.com/vocab/unknown": true}}),
+        "Required vocabulary `https://example.com/vocab/unknown` is not registered."
+    )]
+    fn create_dialect_invalid(raw_meta_schema: RustType, expected_malformed_error_detail: &str) {
+        assert!(matches!(
+            Dialect::create(&meta_schema_url(), &raw_meta_schema, &vocabularies()),
+            Err(SchemaError::Malformed {
+                keyword: KeywordType::Vocabulary,
+                detail,
+                ..
+            }) if detail == expected_malformed_error_detail
+        ));
+    }
+
+    // The dialect is the only source of the keywords, so the scope builder is created with no custom keyword registered
+    fn dialect_schema(vocabulary: Vocabulary, raw_schema: RustType) -> Arc<Schema> {
+        scope_builder_create_and_build(DraftVersion::Draft4, raw_schema, &|scope_builder, generated_url, raw_schema| {
+            let vocabularies = RustType::Object(vec![(vocabulary.uri.to_string(), RustType::Boolean(true))].into_iter().collect());
+            let meta_schema_url = scope_builder.inject_schema(&Arc::new(RustType::Object(vec![("$vocabulary".to_string(), vocabularies)].into_iter().collect())));
+            let _ = scope_builder
+                .register_vocabulary(vocabulary.clone())
+                .use_dialect(&meta_schema_url)
+                .expect("Dialect is supposed to be valid");
+            scope_builder.schema(generated_url, raw_schema)
+        })
+        .expect("Schema is supposed to be valid")
+    }
+
+    #[derive(Debug)]
+    struct NonEmpty;
+
+    impl CustomKeyword for NonEmpty {
+        fn compile(_path: &Url, keyword_value: &RustType) -> Result<Option<Self>, SchemaError> {
+            Ok(if keyword_value.as_boolean() == Some(true) { Some(Self) } else { None })
+        }
+
+        fn validation_errors(&self, path: &str, value: Instance<'_>) -> ValidationErrorIterator {
+            match value.as_string() {
+                Some("") => ValidationErrorIterator::from(ValidationError::new(path, KeywordType::Custom("x-non-empty"), "Empty string")),
+                _ => ValidationErrorIterator::new(EmptyIterator::new()),
+            }
+        }
+    }
+
+    #[test_case(&rust_type!({"a": 1}), &[])]
+    #[test_case(&rust_type!({"a": "text"}), &[])]
+    #[test_case(&rust_type!("text"), &[ValidationError::new("#", KeywordType::Type, "Invalid Type")])]
+    fn validate_with_dialect(object: &RustType, expected_validation_errors: &[ValidationError]) {
+        // The dialect does not include the vocabulary defining `properties`, so only `type` is compiled
+        let schema = dialect_schema(
+            Vocabulary::create(Url::parse("https://example.com/vocab/types").unwrap()).keyword(KeywordType::Type),
+            rust_type!({"type": "object", "properties": {"a": {"type": "string"}}}),
+        );
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+
+    #[test_case(&rust_type!(""), &[ValidationError::new("#", KeywordType::Custom("x-non-empty"), "Empty string")])]
+    #[test_case(&rust_type!("text"), &[])]
+    #[test_case(&rust_type!(1), &[])]
+    fn validate_with_dialect_custom_keyword(object: &RustType, expected_validation_errors: &[ValidationError]) {
+        // The custom keyword is registered only via the vocabulary of the dialect, which does not include `type`
+        let schema = dialect_schema(
+            Vocabulary::create(Url::parse("https://example.com/vocab/strings").unwrap()).custom_keyword::<NonEmpty>("x-non-empty"),
+            rust_type!({"type": "string", "x-non-empty": true}),
+        );
+        assert_eq!(schema.validation_errors("#", object).collect::<Vec<_>>(), expected_validation_errors);
+    }
+}
@@ -0,0 +1,159 @@
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+use test_case::test_case;
+
+fn test_data(file_name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("test-data").join("cli").join(file_name)
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_jsonschema-validator"))
+        .current_dir(test_data(""))
+        .args(args)
+        .output()
+        .expect("The binary is supposed to be executable")
+}
+
+#[test_case(&["validate", "--schema", "schema.json", "valid-instance.json"] => 0)]
+#[test_case(&["validate", "--schema", "schema.json", "invalid-instance.yaml"] => 1)]
+#[test_case(&["validate", "--schema", "schema.json", "valid-instance.json", "invalid-instance.yaml"] => 1)]
+#[test_case(&["validate", "--schema", "schema.json", "valid-instance.json", "missing-instance.json"] => 2)]
+#[test_case(&["validate", "--schema", "schema.json", "non-json-instance.yaml"] => 1)]
+#[test_case(&["validate", "--schema", "schema.json", "yaml-syntax-instance.json"] => 2)]
+#[test_case(&["validate", "--schema", "non-json-schema.yaml", "valid-instance.json"] => 2)]
+#[test_case(&["validate", "--schema", "missing-schema.json", "valid-instance.json"] => 2)]
+#[test_case(&["validate", "--schema", "unsupported-draft-schema.json", "valid-instance.json"] => 2)]
+#[test_case(&["validate", "--schema", "unsupported-draft-schema.json", "--draft", "draft4", "valid-instance.json"] => 0)]
+#[test_case(&["validate", "--schema", "schema.json"] => 2 ; "usage error")]
+fn validate_exit_code(args: &[&str]) -> i32 {
+    run(args).status.code().expect("The binary is not supposed to be terminated by a signal")
+}
+
+#[test]
+fn validate_human_output() {
+    let output = run(&["validate", "--schema", "schema.json", "valid-instance.json", "invalid-instance.yaml"]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "valid-instance.json: valid\ninvalid-instance.yaml: invalid\n  #/port: Invalid Type (Type)\n"
+    );
+}
+
+#[test]
+fn validate_json_output() {
+    let output = run(&[
+        "validate",
+        "--schema",
+        "schema.json",
+        "--error-format",
+        "json",
+        "valid-instance.json",
+        "invalid-instance.yaml",
+    ]);
+    assert_eq!(
+        serde_json::from_slice::<Value>(&output.stdout).unwrap(),
+        json!({
+            "schema": "schema.json",
+            "instances": [
+                {"instance": "valid-instance.json", "valid": true, "errors": []},
+                {"instance": "invalid-instance.yaml", "valid": false, "errors": [{"path": "#/port", "keyword": "Type", "message": "Invalid Type"}]},
+            ],
+        })
+    );
+}
+
+#[test_case(&["check", "check/valid"] => 0)]
+#[test_case(&["check", "check/valid/person.yaml"] => 0)]
+#[test_case(&["check", "check/dangling"] => 2)]
+#[test_case(&["check", "check/valid", "check/dangling"] => 2)]
+#[test_case(&["check", "check/meta-invalid"] => 0)]
+#[test_case(&["check", "--meta-schema", "meta-schema.json", "check/meta-invalid"] => 2)]
+#[test_case(&["check", "--meta-schema", "meta-schema.json", "check/valid"] => 0)]
+#[test_case(&["check", "--meta-schema", "recursive-meta-schema.json", "check/meta-invalid"] => 2)]
+#[test_case(&["check", "--meta-schema", "recursive-meta-schema.json", "check/valid"] => 0)]
+#[test_case(&["check", "check/annotation-refs"] => 0)]
+#[test_case(&["check", "missing-directory"] => 2)]
+fn check_exit_code(args: &[&str]) -> i32 {
+    run(args).status.code().expect("The binary is not supposed to be terminated by a signal")
+}
+
+#[test]
+fn check_human_output() {
+    let output = String::from_utf8(run(&["check", "check/valid", "check/dangling"]).stdout).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 6, "Unexpected output: {}", output);
+    assert_eq!(lines[0], format!("{}: ok", Path::new("check/valid").join("address.json").display()));
+    assert_eq!(lines[1], format!("{}: ok", Path::new("check/valid").join("person.yaml").display()));
+    assert_eq!(lines[2], format!("{}: failed", Path::new("check/dangling").join("person.json").display()));
+    assert!(lines[3].starts_with("  #/properties/address: $ref missing.json cannot be resolved"));
+    assert!(lines[4].starts_with("  #/properties/name: $ref #/definitions/name cannot be resolved"));
+    assert_eq!(lines[5], "3 schemas checked, 1 failed");
+}
+
+#[test]
+fn check_json_output() {
+    let output = run(&["check", "--error-format", "json", "--meta-schema", "meta-schema.json", "check/meta-invalid"]);
+    assert_eq!(
+        serde_json::from_slice::<Value>(&output.stdout).unwrap(),
+        json!({
+            "schemas": [{
+                "schema": Path::new("check/meta-invalid").join("person.json").display().to_string(),
+                "valid": false,
+                "errors": ["#/properties/name/type: Invalid Type (meta-schema Type)"],
+            }],
+        })
+    );
+}
+
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "instances.ndjson"] => 1)]
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "--jobs", "4", "instances.ndjson"] => 1)]
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "valid-instance.json"] => 0)]
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "missing-instance.ndjson"] => 2)]
+#[test_case(&["validate", "--schema", "schema.json", "--jobs", "4", "valid-instance.json"] => 2 ; "jobs requires ndjson")]
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "--jobs", "0", "instances.ndjson"] => 2 ; "jobs is positive")]
+#[test_case(&["validate", "--schema", "schema.json", "--ndjson", "--jobs", "many", "instances.ndjson"] => 2 ; "jobs is numeric")]
+fn validate_ndjson_exit_code(args: &[&str]) -> i32 {
+    run(args).status.code().expect("The binary is not supposed to be terminated by a signal")
+}
+
+#[test]
+fn validate_ndjson_human_output() {
+    let output = String::from_utf8(run(&["validate", "--schema", "schema.json", "--ndjson", "instances.ndjson"]).stdout).unwrap();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5, "Unexpected output: {}", output);
+    assert_eq!(lines[0], "instances.ndjson:3: invalid");
+    assert_eq!(lines[1], "  #/port: Invalid Type (Type)");
+    assert_eq!(lines[2], "instances.ndjson:4: invalid");
+    assert!(lines[3].starts_with("  #: Invalid JSON: "));
+    assert_eq!(lines[4], "instances.ndjson: 3 records, 2 invalid");
+}
+
+#[test]
+fn validate_ndjson_json_output() {
+    let output = String::from_utf8(
+        run(&[
+            "validate",
+            "--schema",
+            "schema.json",
+            "--ndjson",
+            "--error-format",
+            "json",
+            "--jobs",
+            "2",
+            "instances.ndjson",
+        ])
+        .stdout,
+    )
+    .unwrap();
+    let records = output.lines().map(|line| serde_json::from_str::<Value>(line).unwrap()).collect::<Vec<_>>();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0], json!({"instance": "instances.ndjson", "line": 1, "valid": true, "errors": []}));
+    assert_eq!(
+        records[1],
+        json!({"instance": "instances.ndjson", "line": 3, "valid": false, "errors": [{"path": "#/port", "keyword": "Type", "message": "Invalid Type"}]})
+    );
+    assert_eq!(records[2]["line"], json!(4));
+    assert_eq!(records[2]["valid"], json!(false));
+}
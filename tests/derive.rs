@@ -0,0 +1,218 @@
+use json_trait_rs::rust_type;
+use jsonschema_validator::{DraftVersion, JsonSchema, KeywordType, ScopeBuilder, ValidationError};
+use loader_rs::loaders::RustTypeLoader;
+use std::collections::HashMap;
+
+#[derive(JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Person {
+    first_name: String,
+    #[serde(rename = "years")]
+    age: Option<u8>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    nick_names: Vec<String>,
+    #[serde(skip)]
+    cache: HashMap<String, String>,
+    #[serde(flatten)]
+    address: Address,
+}
+
+#[derive(JsonSchema)]
+struct Address {
+    street: String,
+    #[serde(default)]
+    number: u32,
+}
+
+#[derive(JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum Colour {
+    LightRed,
+    #[serde(rename = "dark-blue")]
+    DarkBlue,
+}
+
+#[derive(JsonSchema)]
+#[serde(tag = "kind")]
+enum Shape {
+    Circle { radius: f64 },
+    Point,
+}
+
+#[derive(JsonSchema)]
+#[serde(tag = "t", content = "c")]
+enum Message {
+    Text(String),
+    Ping,
+}
+
+#[derive(JsonSchema)]
+enum Value {
+    Flag(bool),
+    Pair(i32, i32),
+    Nothing,
+}
+
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum Id {
+    Number(u64),
+    Name(String),
+}
+
+#[derive(JsonSchema)]
+struct Wrapper<T>(T);
+
+#[derive(JsonSchema)]
+struct Tree {
+    value: u8,
+    children: Vec<Tree>,
+}
+
+#[derive(JsonSchema)]
+struct Forest {
+    trees: Vec<Tree>,
+    tallest: Option<Box<Tree>>,
+}
+
+#[derive(JsonSchema)]
+struct Directory {
+    size: u64,
+    entries: HashMap<String, Directory>,
+}
+
+#[test]
+fn struct_schema_honours_serde_attributes() {
+    assert_eq!(
+        Person::json_schema(),
+        rust_type!({
+            "type": "object",
+            "properties": {
+                "firstName": {"type": "string"},
+                "years": {"type": ["integer", "null"]},
+                "nickNames": {"type": "array", "items": {"type": "string"}},
+                "street": {"type": "string"},
+                "number": {"type": "integer"},
+            },
+            "required": ["firstName", "street"],
+            "additionalProperties": false,
+        })
+    );
+}
+
+#[test]
+fn enum_schemas_honour_serde_representations() {
+    assert_eq!(Colour::json_schema(), rust_type!({"type": "string", "enum": ["light_red", "dark-blue"]}));
+    assert_eq!(
+        Shape::json_schema(),
+        rust_type!({"oneOf": [
+            {
+                "type": "object",
+                "properties": {"kind": {"type": "string", "enum": ["Circle"]}, "radius": {"type": "number"}},
+                "required": ["kind", "radius"],
+            },
+            {"type": "object", "properties": {"kind": {"type": "string", "enum": ["Point"]}}, "required": ["kind"]},
+        ]})
+    );
+    assert_eq!(
+        Message::json_schema(),
+        rust_type!({"oneOf": [
+            {
+                "type": "object",
+                "properties": {"t": {"type": "string", "enum": ["Text"]}, "c": {"type": "string"}},
+                "required": ["t", "c"],
+                "additionalProperties": false,
+            },
+            {"type": "object", "properties": {"t": {"type": "string", "enum": ["Ping"]}}, "required": ["t"], "additionalProperties": false},
+        ]})
+    );
+    assert_eq!(
+        Value::json_schema(),
+        rust_type!({"oneOf": [
+            {"type": "object", "properties": {"Flag": {"type": "boolean"}}, "required": ["Flag"], "additionalProperties": false},
+            {
+                "type": "object",
+                "properties": {"Pair": {"type": "array", "items": [{"type": "integer"}, {"type": "integer"}]}},
+                "required": ["Pair"],
+                "additionalProperties": false,
+            },
+            {"type": "string", "enum": ["Nothing"]},
+        ]})
+    );
+    assert_eq!(Id::json_schema(), rust_type!({"oneOf": [{"type": "integer"}, {"type": "string"}]}));
+}
+
+#[test]
+fn generic_schema_uses_the_schema_of_the_parameters() {
+    assert_eq!(Wrapper::<Option<bool>>::json_schema(), rust_type!({"type": ["boolean", "null"]}));
+}
+
+#[test]
+fn recursive_schemas_are_referenced() {
+    assert_eq!(
+        Tree::json_schema(),
+        rust_type!({
+            "type": "object",
+            "properties": {"value": {"type": "integer"}, "children": {"type": "array", "items": {"$ref": "#"}}},
+            "required": ["value", "children"],
+        })
+    );
+    assert_eq!(
+        Forest::json_schema(),
+        rust_type!({
+            "type": "object",
+            "properties": {
+                "trees": {"type": "array", "items": {"$ref": "#/definitions/Tree"}},
+                "tallest": {"oneOf": [{"$ref": "#/definitions/Tree"}, {"type": "null"}]},
+            },
+            "required": ["trees"],
+            "definitions": {
+                "Tree": {
+                    "type": "object",
+                    "properties": {"value": {"type": "integer"}, "children": {"type": "array", "items": {"$ref": "#/definitions/Tree"}}},
+                    "required": ["value", "children"],
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn compiled_recursive_schema_validates_instances() {
+    assert!(Forest::compiled_schema().is_ok());
+
+    let schema = Directory::compiled_schema().expect("Schema is supposed to be valid");
+    assert!(schema.is_valid("#", &rust_type!({"size": 1, "entries": {"bin": {"size": 2, "entries": {}}}})));
+    assert_eq!(
+        schema
+            .validation_errors("#", &rust_type!({"size": 1, "entries": {"bin": {"size": "2", "entries": {}}}}))
+            .collect::<Vec<_>>(),
+        vec![ValidationError::new("#/entries/bin/size", KeywordType::Type, "Invalid Type")]
+    );
+}
+
+#[test]
+fn compiled_schema_validates_instances() {
+    let schema = Person::compiled_schema().expect("Schema is supposed to be valid");
+    assert!(schema.is_valid("#", &rust_type!({"firstName": "Jane", "years": null, "street": "Main Street"})));
+    let mut validation_errors = schema
+        .validation_errors("#", &rust_type!({"firstName": 1, "street": "Main Street", "phone": "0"}))
+        .collect::<Vec<_>>();
+    validation_errors.sort_by(|left, right| left.path().cmp(right.path()));
+    assert_eq!(
+        validation_errors,
+        vec![
+            ValidationError::new("#/firstName", KeywordType::Type, "Invalid Type"),
+            ValidationError::new("#/phone", KeywordType::AdditionalProperties, "Additional property is not allowed"),
+        ]
+    );
+}
+
+#[test]
+fn scope_builder_compiles_derived_schemas() {
+    let mut scope_builder = ScopeBuilder::create(DraftVersion::Draft4, RustTypeLoader::default());
+    let schema = scope_builder.json_schema::<Address>().expect("Schema is supposed to be valid");
+    let _ = scope_builder.build();
+    assert!(schema.is_valid("#", &rust_type!({"street": "Main Street"})));
+    assert!(!schema.is_valid("#", &rust_type!({"street": 1})));
+}
@@ -0,0 +1,213 @@
+use json_trait_rs::{JsonType, RustType};
+use jsonschema_validator::{DraftVersion, ScopeBuilder};
+use loader_rs::{loaders::RustTypeLoader, LoaderTrait};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use url::Url;
+
+// Base URL used by the test suite to reference the content of the `remotes` directory
+const REMOTES_BASE_URL: &str = "http://localhost:1234/";
+
+// Known unsupported cases as (test file, group description, reason).
+// A `None` group description skips all the groups defined in the test file.
+// Entries not matching any test file or group of the suite fail the harness, so the list is kept in sync with the suite.
+const SKIP_LIST: &[(&str, Option<&str>, &str)] = &[
+    ("draft4/additionalItems.json", None, "additionalItems keyword is not supported"),
+    ("draft4/allOf.json", None, "required, maximum, anyOf and oneOf keywords are not supported"),
+    ("draft4/anyOf.json", None, "anyOf keyword is not supported"),
+    ("draft4/definitions.json", None, "validation against the draft4 meta-schema is not supported"),
+    ("draft4/dependencies.json", None, "dependencies keyword is not supported"),
+    ("draft4/enum.json", None, "enum keyword is not supported"),
+    ("draft4/format.json", None, "format keyword is not supported"),
+    ("draft4/items.json", None, "items keyword is not supported"),
+    ("draft4/maxItems.json", None, "maxItems keyword is not supported"),
+    ("draft4/maxLength.json", None, "maxLength keyword is not supported"),
+    ("draft4/maxProperties.json", None, "maxProperties keyword is not supported"),
+    ("draft4/maximum.json", None, "maximum keyword is not supported"),
+    ("draft4/minItems.json", None, "minItems keyword is not supported"),
+    ("draft4/minLength.json", None, "minLength keyword is not supported"),
+    ("draft4/minProperties.json", None, "minProperties keyword is not supported"),
+    ("draft4/minimum.json", None, "minimum keyword is not supported"),
+    ("draft4/multipleOf.json", None, "multipleOf keyword is not supported"),
+    ("draft4/not.json", None, "not keyword is not supported"),
+    ("draft4/oneOf.json", None, "oneOf keyword is not supported"),
+    ("draft4/pattern.json", None, "pattern keyword is not supported"),
+    (
+        "draft4/patternProperties.json",
+        Some("multiple simultaneous patternProperties are validated"),
+        "maximum keyword is not supported",
+    ),
+    ("draft4/required.json", None, "required keyword is not supported"),
+    ("draft4/uniqueItems.json", None, "uniqueItems keyword is not supported"),
+    (
+        "draft4/properties.json",
+        Some("properties, patternProperties, additionalProperties interaction"),
+        "maxItems and minItems keywords are not supported",
+    ),
+    ("draft4/ref.json", Some("relative pointer ref to array"), "items keyword is not supported"),
+    ("draft4/ref.json", Some("remote ref, containing refs itself"), "validation against the draft4 meta-schema is not supported"),
+    ("draft4/ref.json", Some("Recursive references between schemas"), "id keyword is not supported"),
+    ("draft4/ref.json", Some("Location-independent identifier"), "id keyword is not supported"),
+    ("draft4/ref.json", Some("Location-independent identifier with base URI change in subschema"), "id keyword is not supported"),
+    ("draft4/ref.json", Some("naive replacement of $ref with its destination is not correct"), "enum keyword is not supported"),
+    ("draft4/refRemote.json", Some("base URI change"), "id keyword is not supported"),
+    ("draft4/refRemote.json", Some("base URI change - change folder"), "id keyword is not supported"),
+    ("draft4/refRemote.json", Some("base URI change - change folder in subschema"), "id keyword is not supported"),
+    ("draft4/refRemote.json", Some("root ref in remote ref"), "id keyword is not supported"),
+];
+
+fn is_skipped(test_file: &str, group_description: Option<&str>) -> bool {
+    SKIP_LIST
+        .iter()
+        .any(|(skipped_file, skipped_group, _)| skipped_file == &test_file && (skipped_group.is_none() || skipped_group == &group_description))
+}
+
+fn test_suite_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("JSON-Schema-Test-Suite")
+}
+
+fn load(path: &Path) -> Arc<RustType> {
+    RustTypeLoader::default()
+        .get_or_fetch_with_result(&Url::from_file_path(path).unwrap())
+        .unwrap_or_else(|error| panic!("Unable to load {}: {:?}", path.display(), error))
+}
+
+fn json_files(directory: &Path) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(directory)
+        .unwrap_or_else(|error| panic!("Unable to read {}: {:?}", directory.display(), error))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file() && path.extension().map_or(false, |extension| extension == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+// Remote documents are loaded once and then injected in the loader cache of each ScopeBuilder,
+// so references to REMOTES_BASE_URL are resolved without any network interaction
+fn load_remotes(remotes_directory: &Path) -> Vec<(Url, Arc<RustType>)> {
+    let mut remotes = Vec::new();
+    let mut directories = vec![remotes_directory.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(&directory).unwrap_or_else(|error| panic!("Unable to read {}: {:?}", directory.display(), error)) {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().map_or(false, |extension| extension == "json") {
+                let relative_path = path
+                    .strip_prefix(remotes_directory)
+                    .unwrap()
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                remotes.push((Url::parse(REMOTES_BASE_URL).unwrap().join(&relative_path).unwrap(), load(&path)));
+            }
+        }
+    }
+    remotes
+}
+
+fn remotes_loader(remotes: &[(Url, Arc<RustType>)]) -> RustTypeLoader {
+    let loader = RustTypeLoader::default();
+    for (url, raw_remote) in remotes {
+        loader.save_in_cache(url, raw_remote);
+    }
+    loader
+}
+
+fn run_test_suite(draft_directory_name: &str, draft_version: DraftVersion) {
+    let test_suite_directory = test_suite_path();
+    let draft_directory = test_suite_directory.join("tests").join(draft_directory_name);
+    assert!(
+        draft_directory.is_dir(),
+        "{} does not exist. Run `git submodule update --init` to fetch JSON-Schema-Test-Suite",
+        draft_directory.display()
+    );
+    let remotes = load_remotes(&test_suite_directory.join("remotes"));
+
+    let mut executed_test_cases = 0;
+    let mut failures = Vec::new();
+    // Test files and groups of the suite, used to detect stale SKIP_LIST entries
+    let mut suite_groups = Vec::new();
+    for test_file_path in json_files(&draft_directory) {
+        let test_file = format!("{}/{}", draft_directory_name, test_file_path.file_name().unwrap().to_string_lossy());
+        suite_groups.push((test_file.clone(), None));
+        let test_groups = load(&test_file_path);
+        let test_groups = test_groups.as_array().expect("Test files are expected to contain an array");
+        suite_groups.extend(
+            test_groups
+                .iter()
+                .filter_map(|test_group| test_group.get_attribute("description").and_then(|value| value.as_string()))
+                .map(|group_description| (test_file.clone(), Some(group_description.to_string()))),
+        );
+        if is_skipped(&test_file, None) {
+            continue;
+        }
+
+        for test_group in test_groups {
+            let group_description = test_group.get_attribute("description").and_then(|value| value.as_string()).unwrap();
+            if is_skipped(&test_file, Some(group_description)) {
+                continue;
+            }
+
+            let raw_schema = Arc::new(test_group.get_attribute("schema").unwrap().clone());
+            let mut scope_builder = ScopeBuilder::create(draft_version, remotes_loader(&remotes));
+            let generated_url = scope_builder.inject_schema(&raw_schema);
+            let schema = match scope_builder.schema(&generated_url, &*raw_schema) {
+                Ok(schema) => schema,
+                Err(schema_error) => {
+                    failures.push(format!("{} -> {}: schema compilation failed: {}", test_file, group_description, schema_error));
+                    continue;
+                }
+            };
+            let _ = scope_builder.build();
+
+            for test_case in test_group.get_attribute("tests").and_then(|value| value.as_array()).unwrap() {
+                let description = test_case.get_attribute("description").and_then(|value| value.as_string()).unwrap();
+                let data = test_case.get_attribute("data").unwrap();
+                let expected_valid = test_case.get_attribute("valid").and_then(|value| value.as_boolean()).unwrap();
+                executed_test_cases += 1;
+
+                let is_valid = schema.is_valid("#", data);
+                let validation_errors = schema.validation_errors("#", data).collect::<Vec<_>>();
+                if is_valid != expected_valid || validation_errors.is_empty() != expected_valid {
+                    failures.push(format!(
+                        "{} -> {} -> {}: expected valid={}, is_valid={}, validation_errors={:?}",
+                        test_file, group_description, description, expected_valid, is_valid, validation_errors
+                    ));
+                }
+            }
+        }
+    }
+
+    failures.extend(
+        SKIP_LIST
+            .iter()
+            .filter(|(skipped_file, _, _)| skipped_file.starts_with(&format!("{}/", draft_directory_name)))
+            .filter(|(skipped_file, skipped_group, _)| {
+                !suite_groups
+                    .iter()
+                    .any(|(test_file, group_description)| test_file == skipped_file && group_description.as_deref() == *skipped_group)
+            })
+            .map(|(skipped_file, skipped_group, reason)| format!("{} -> {:?}: stale SKIP_LIST entry ({})", skipped_file, skipped_group, reason)),
+    );
+
+    assert!(
+        failures.is_empty(),
+        "{} out of {} test cases failed:\n{}",
+        failures.len(),
+        executed_test_cases,
+        failures.join("\n")
+    );
+}
+
+// The suite is a git submodule, which is not checked out by a plain clone, so the harness is opt-in:
+// `cargo test --test json_schema_test_suite -- --ignored` (run by `make test`) fails if the suite is missing
+#[test]
+#[ignore]
+fn draft4() {
+    run_test_suite("draft4", DraftVersion::Draft4);
+}